@@ -0,0 +1,319 @@
+use crate::solver::{Part, Registry, SolveError};
+use crate::utils::io::InputSource;
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use std::{fmt, fs, io};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Counts every allocation going through the system allocator.
+/// Must be installed as the `#[global_allocator]` of the binary, otherwise allocations read as 0
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Cost of a single run of a single phase
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sample {
+    pub elapsed: Duration,
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+}
+
+/// Counters are process-wide: anything allocating on another thread meanwhile is accounted for too
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Sample) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let res = f();
+    let elapsed = start.elapsed();
+    let sample = Sample {
+        elapsed,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    };
+    (res, sample)
+}
+
+/// An answer along with what parsing the input, then solving, did cost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measured {
+    pub answer: String,
+    pub parse: Sample,
+    pub solve: Sample,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Summary {
+    pub min: u64,
+    pub median: u64,
+    pub max: u64,
+}
+
+impl Summary {
+    fn of(mut values: Vec<u64>) -> Self {
+        values.sort_unstable();
+        Summary {
+            min: values.first().copied().unwrap_or(0),
+            median: values.get(values.len() / 2).copied().unwrap_or(0),
+            max: values.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseReport {
+    pub nanos: Summary,
+    pub allocations: Summary,
+    pub allocated_bytes: Summary,
+}
+
+impl PhaseReport {
+    fn of(samples: &[Sample]) -> Self {
+        let summary = |f: fn(&Sample) -> u64| Summary::of(samples.iter().map(f).collect());
+        PhaseReport {
+            nanos: summary(|s| s.elapsed.as_nanos() as u64),
+            allocations: summary(|s| s.allocations),
+            allocated_bytes: summary(|s| s.allocated_bytes),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    /// `None` when the part could not be solved, see `error`
+    pub answer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub parse: PhaseReport,
+    pub solve: PhaseReport,
+}
+
+impl PartReport {
+    /// Median of parse + solve
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.parse.nanos.median + self.solve.nanos.median)
+    }
+}
+
+/// Results of a benchmark session, meant to be stored and compared with the ones of another commit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub runs: usize,
+    pub parts: Vec<PartReport>,
+}
+
+/// How the median of a (day, part) evolved since the baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Change {
+    /// `current / baseline`, above 1 being slower
+    pub fn ratio(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} part {}: {:?} -> {:?} (x{:.2})",
+            self.day,
+            self.part,
+            self.baseline,
+            self.current,
+            self.ratio()
+        )
+    }
+}
+
+impl Report {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, format!("{content}\n"))
+    }
+
+    /// Parts solved in both reports, ordered by day then part
+    pub fn compare(&self, baseline: &Report) -> Vec<Change> {
+        self.parts
+            .iter()
+            .filter(|current| current.answer.is_some())
+            .filter_map(|current| {
+                baseline
+                    .parts
+                    .iter()
+                    .find(|b| b.day == current.day && b.part == current.part && b.answer.is_some())
+                    .map(|b| Change {
+                        day: current.day,
+                        part: current.part,
+                        baseline: b.median(),
+                        current: current.median(),
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Runs every registered (day, part) `runs` times, or only the ones of `days` when not empty
+pub fn bench(registry: &Registry, source: &InputSource, days: &[u8], runs: usize) -> Report {
+    let mut parts = vec![];
+    for solver in registry.entries() {
+        if !days.is_empty() && !days.contains(&solver.day) {
+            continue;
+        }
+        let mut report = PartReport {
+            day: solver.day,
+            part: match solver.part {
+                Part::One => 1,
+                Part::Two => 2,
+            },
+            answer: None,
+            error: None,
+            parse: PhaseReport::default(),
+            solve: PhaseReport::default(),
+        };
+        let input = match source.read(solver.day) {
+            Ok(input) => input,
+            Err(e) => {
+                report.error = Some(e.to_string());
+                parts.push(report);
+                continue;
+            }
+        };
+        let mut parse = vec![];
+        let mut solve = vec![];
+        for _ in 0..runs.max(1) {
+            match solver.solve_measured(input.as_str()) {
+                Ok(measured) => {
+                    parse.push(measured.parse);
+                    solve.push(measured.solve);
+                    report.answer = Some(measured.answer);
+                }
+                Err(e) => {
+                    report.error = Some(match e {
+                        SolveError::Unsolved => "unsolved".to_string(),
+                        e => e.to_string(),
+                    });
+                    break;
+                }
+            }
+        }
+        if report.error.is_none() {
+            report.parse = PhaseReport::of(&parse);
+            report.solve = PhaseReport::of(&solve);
+        }
+        parts.push(report);
+    }
+    Report { runs, parts }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::{bench, measure, Report, Summary};
+    use crate::solver::Registry;
+    use crate::utils::io::website_inputs;
+    use std::time::Duration;
+
+    #[test]
+    fn summarizes() {
+        let summary = Summary::of(vec![5, 1, 4, 2, 3]);
+        assert_eq!(
+            Summary {
+                min: 1,
+                median: 3,
+                max: 5
+            },
+            summary
+        );
+        assert_eq!(Summary::default(), Summary::of(vec![]));
+    }
+
+    #[test]
+    fn measures_elapsed_time() {
+        let (res, sample) = measure(|| {
+            std::thread::sleep(Duration::from_millis(5));
+            42
+        });
+        assert_eq!(42, res);
+        assert!(sample.elapsed >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn benches_parse_and_solve_apart() {
+        let report = bench(&Registry::default(), &website_inputs(), &[4, 7], 3);
+        assert_eq!(3, report.runs);
+        let days = report
+            .parts
+            .iter()
+            .map(|p| (p.day, p.part))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(4, 1), (4, 2), (7, 1), (7, 2)], days);
+        let day_4 = &report.parts[1];
+        assert_eq!(Some("895".to_string()), day_4.answer);
+        assert!(day_4.parse.nanos.min <= day_4.parse.nanos.median);
+        assert!(day_4.parse.nanos.median <= day_4.parse.nanos.max);
+        assert!(day_4.parse.nanos.median > 0);
+        assert_eq!(None, report.parts[3].error);
+        assert_eq!(Some("549173".to_string()), report.parts[3].answer);
+    }
+
+    #[test]
+    fn compares_with_baseline() {
+        let current = bench(&Registry::default(), &website_inputs(), &[6], 1);
+        let mut baseline = current.clone();
+        for part in baseline.parts.iter_mut() {
+            part.solve.nanos.median = current.parts[0].median().as_nanos() as u64 * 2;
+            part.parse.nanos.median = 0;
+        }
+        let changes = current.compare(&baseline);
+        assert_eq!(2, changes.len());
+        assert!(changes[0].ratio() < 1.0);
+
+        let json = serde_json::to_string(&current).unwrap();
+        assert_eq!(current, serde_json::from_str::<Report>(&json).unwrap());
+    }
+}
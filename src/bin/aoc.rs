@@ -0,0 +1,618 @@
+use advent_of_code::bench::{bench, CountingAllocator, Report};
+use advent_of_code::client::{Client, Ledger, Session, Verdict};
+use advent_of_code::solver::{Part, PartSolver, Registry, SolveError};
+use advent_of_code::utils::io::{InputError, InputSource};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use std::{env, io};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+Usage:
+  aoc run --day <N> [--part <1|2>] [--input <PATH|->] [--inputs <DIR>] [--user <NAME>]
+  aoc run --all [--inputs <DIR>] [--user <NAME>]
+  aoc fetch --day <N> [--inputs <DIR>] [--user <NAME>]
+  aoc submit --day <N> --part <1|2> [--answer <ANSWER>] [--inputs <DIR>] [--user <NAME>]
+  aoc bench [--day <N>] [--runs <N>] [--output <PATH>] [--baseline <PATH>] [--inputs <DIR>] [--user <NAME>]
+
+Options:
+  --day <N>        day of the calendar to run
+  --part <1|2>     only run this part (both parts by default)
+  --input <PATH>   puzzle input, `-` reads it from stdin (defaults to <DIR>/day_<N>.txt)
+  --all            run every registered day and print a summary
+  --inputs <DIR>   directory containing the day_<N>.txt inputs
+  --user <NAME>    read inputs from <DIR>/<NAME>/ instead
+  --answer <A>     answer to submit (computed from the input by default)
+  --runs <N>       how many times each part is benchmarked (10 by default)
+  --output <PATH>  where to store the benchmark results, as JSON
+  --baseline <PATH> benchmark results of a previous run to compare with
+
+Unless given, the inputs directory and user come from $AOC_INPUTS and $AOC_USER,
+then from the config file ($AOC_CONFIG or ~/.config/aoc/config.json), then ./website_inputs.
+`fetch` downloads the input into that directory, unless already there, using the session
+cookie from $AOC_SESSION or the `session` entry of the config file.
+`submit` records the verdicts in <DIR>/answers.json, and won't send an answer already
+known to be wrong or while a cooldown is pending.
+`bench` times parsing and solving apart, for every registered day unless --day is given.";
+
+const DEFAULT_RUNS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+/// Overrides of the `InputSource` resolved from the environment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Inputs {
+    dir: Option<PathBuf>,
+    user: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Run {
+        day: u8,
+        part: Option<Part>,
+        input: Option<Input>,
+        inputs: Inputs,
+    },
+    RunAll {
+        inputs: Inputs,
+    },
+    Fetch {
+        day: u8,
+        inputs: Inputs,
+    },
+    Submit {
+        day: u8,
+        part: Part,
+        answer: Option<String>,
+        inputs: Inputs,
+    },
+    Bench {
+        day: Option<u8>,
+        runs: usize,
+        output: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+        inputs: Inputs,
+    },
+}
+
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        Some(command) if ["run", "fetch", "submit", "bench"].contains(&command.as_str()) => command,
+        Some(other) => return Err(format!("Unknown command {other:?}")),
+        None => return Err("Missing command".to_string()),
+    };
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut answer = None;
+    let mut runs = None;
+    let mut output = None;
+    let mut baseline = None;
+    let mut all = false;
+    let mut inputs = Inputs::default();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {arg}"));
+        match arg.as_str() {
+            "--day" => {
+                let value = value()?;
+                day = Some(
+                    value
+                        .parse::<u8>()
+                        .map_err(|_| format!("Invalid day {value:?}"))?,
+                );
+            }
+            "--part" => {
+                let value = value()?;
+                let number = value
+                    .parse::<u8>()
+                    .map_err(|_| format!("Invalid part {value:?}"))?;
+                part = Some(Part::try_from(number)?);
+            }
+            "--input" => {
+                let value = value()?;
+                input = Some(if value == "-" {
+                    Input::Stdin
+                } else {
+                    Input::File(PathBuf::from(value))
+                });
+            }
+            "--answer" => answer = Some(value()?),
+            "--runs" => {
+                let value = value()?;
+                runs = Some(
+                    value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|runs| *runs > 0)
+                        .ok_or(format!("Invalid number of runs {value:?}"))?,
+                );
+            }
+            "--output" => output = Some(PathBuf::from(value()?)),
+            "--baseline" => baseline = Some(PathBuf::from(value()?)),
+            "--inputs" => inputs.dir = Some(PathBuf::from(value()?)),
+            "--user" => inputs.user = Some(value()?),
+            "--all" => all = true,
+            _ => return Err(format!("Unknown option {arg:?}")),
+        }
+    }
+    if command != "bench" && (runs.is_some() || output.is_some() || baseline.is_some()) {
+        return Err("--runs, --output and --baseline only apply to bench".to_string());
+    }
+    match command.as_str() {
+        "bench" => {
+            return if all || part.is_some() || input.is_some() || answer.is_some() {
+                Err(
+                    "bench only accepts --day, --runs, --output, --baseline, --inputs and --user"
+                        .to_string(),
+                )
+            } else {
+                Ok(Command::Bench {
+                    day,
+                    runs: runs.unwrap_or(DEFAULT_RUNS),
+                    output,
+                    baseline,
+                    inputs,
+                })
+            }
+        }
+        "fetch" => {
+            return match day {
+                Some(day) if !all && part.is_none() && input.is_none() && answer.is_none() => {
+                    Ok(Command::Fetch { day, inputs })
+                }
+                Some(_) => Err("fetch only accepts --day, --inputs and --user".to_string()),
+                None => Err("fetch expects --day".to_string()),
+            }
+        }
+        "submit" => {
+            return match (day, part) {
+                (Some(day), Some(part)) if !all && input.is_none() => Ok(Command::Submit {
+                    day,
+                    part,
+                    answer,
+                    inputs,
+                }),
+                (Some(_), Some(_)) => {
+                    Err("submit can't be combined with --all or --input".to_string())
+                }
+                _ => Err("submit expects --day and --part".to_string()),
+            }
+        }
+        _ if answer.is_some() => return Err("--answer only applies to submit".to_string()),
+        _ => {}
+    }
+    match (all, day) {
+        (true, None) if part.is_none() && input.is_none() => Ok(Command::RunAll { inputs }),
+        (true, None) => Err("--all can't be combined with --part or --input".to_string()),
+        (true, Some(_)) => Err("--all can't be combined with --day".to_string()),
+        (false, None) => Err("Expecting either --day or --all".to_string()),
+        (false, Some(day)) => Ok(Command::Run {
+            day,
+            part,
+            input,
+            inputs,
+        }),
+    }
+}
+
+fn input_source(inputs: Inputs) -> Result<InputSource, InputError> {
+    let resolved = InputSource::from_env()?;
+    let (dir, user) = match resolved {
+        InputSource::Directory(dir) => (dir, None),
+        InputSource::UserDirectory { root, user } => (root, Some(user)),
+        other => return Ok(other),
+    };
+    let dir = inputs.dir.unwrap_or(dir);
+    Ok(match inputs.user.or(user) {
+        Some(user) => InputSource::UserDirectory { root: dir, user },
+        None => InputSource::Directory(dir),
+    })
+}
+
+fn single_input_source(
+    day: u8,
+    input: Option<Input>,
+    inputs: Inputs,
+) -> Result<InputSource, String> {
+    match input {
+        Some(Input::Stdin) => {
+            let mut buf = String::new();
+            io::stdin()
+                .read_to_string(&mut buf)
+                .map_err(|e| format!("Could not read stdin: {e}"))?;
+            Ok(InputSource::in_memory(day, buf))
+        }
+        Some(Input::File(path)) => Ok(InputSource::File(path)),
+        None => input_source(inputs).map_err(|e| e.to_string()),
+    }
+}
+
+fn timed(solver: &PartSolver, input: &str) -> (Result<String, SolveError>, Duration) {
+    let start = Instant::now();
+    let res = solver.solve(input);
+    (res, start.elapsed())
+}
+
+fn run(registry: &Registry, day: u8, parts: &[Part], source: &InputSource) -> Result<(), String> {
+    let content = source
+        .read(day)
+        .map_err(|e| format!("Could not read input: {e}"))?;
+    let mut failed = false;
+    for part in parts {
+        let solver = registry
+            .get(day, *part)
+            .ok_or(format!("Day {day} is not registered"))?;
+        match solver.solve(content.as_str()) {
+            Ok(answer) => println!("{answer}"),
+            Err(e) => {
+                eprintln!("day {day} part {part}: {e}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        Err("Some parts could not be solved".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+struct Row {
+    day: u8,
+    part: Part,
+    outcome: String,
+    elapsed: Option<Duration>,
+}
+
+fn print_summary(rows: &[Row]) {
+    let width = rows
+        .iter()
+        .map(|row| row.outcome.len())
+        .max()
+        .unwrap_or(0)
+        .max("answer".len());
+    println!("day | part | {:<width$} | time", "answer");
+    println!("----|------|-{}-|---------", "-".repeat(width));
+    for row in rows {
+        let elapsed = row
+            .elapsed
+            .map(|d| format!("{:.3}ms", d.as_secs_f64() * 1_000.0))
+            .unwrap_or_default();
+        let line = format!(
+            "{:>3} | {:>4} | {:<width$} | {elapsed}",
+            row.day, row.part, row.outcome
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+fn run_all(registry: &Registry, source: &InputSource) -> Result<(), String> {
+    let mut rows = vec![];
+    let mut failed = false;
+    for day in registry.days() {
+        let content = source.read(day);
+        for part in Part::ALL {
+            let solver = registry.get(day, part).unwrap();
+            let (outcome, elapsed) = match &content {
+                Err(e) => (format!("error: {e}"), None),
+                Ok(content) => match timed(&solver, content.as_str()) {
+                    (Ok(answer), elapsed) => (answer, Some(elapsed)),
+                    (Err(SolveError::Unsolved), _) => ("unsolved".to_string(), None),
+                    (Err(e), elapsed) => (format!("error: {e}"), Some(elapsed)),
+                },
+            };
+            failed |= outcome.starts_with("error");
+            rows.push(Row {
+                day,
+                part,
+                outcome,
+                elapsed,
+            });
+        }
+    }
+    print_summary(&rows);
+    if failed {
+        Err("Some days could not be solved".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn fetch(day: u8, source: &InputSource) -> Result<(), String> {
+    let session = Session::from_env().map_err(|e| e.to_string())?;
+    let mut client = Client::new(session);
+    client
+        .input(day, source)
+        .map_err(|e| format!("Could not fetch input: {e}"))?;
+    if let Some(path) = source.path(day) {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn submit(
+    registry: &Registry,
+    day: u8,
+    part: Part,
+    answer: Option<String>,
+    source: &InputSource,
+) -> Result<(), String> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solver = registry
+                .get(day, part)
+                .ok_or(format!("Day {day} is not registered"))?;
+            let content = source
+                .read(day)
+                .map_err(|e| format!("Could not read input: {e}"))?;
+            solver
+                .solve(content.as_str())
+                .map_err(|e| format!("day {day} part {part}: {e}"))?
+        }
+    };
+    let mut ledger = Ledger::for_inputs(source).map_err(|e| e.to_string())?;
+    let session = Session::from_env().map_err(|e| e.to_string())?;
+    let verdict = Client::new(session)
+        .submit(day, part, &answer, &mut ledger)
+        .map_err(|e| format!("{answer}: {e}"))?;
+    match verdict {
+        Verdict::Correct => {
+            println!("{answer}: right answer");
+            Ok(())
+        }
+        Verdict::Incorrect { hint, wait } => {
+            let hint = match hint {
+                Some(hint) => format!(" ({hint:?})"),
+                None => String::new(),
+            };
+            let wait = wait
+                .map(|w| format!(", wait {}s before trying again", w.as_secs()))
+                .unwrap_or_default();
+            Err(format!("{answer}: wrong answer{hint}{wait}"))
+        }
+        Verdict::TooSoon(wait) => Err(format!(
+            "{answer}: not checked, wait {}s before trying again",
+            wait.as_secs()
+        )),
+        Verdict::WrongLevel => Err(format!(
+            "{answer}: not checked, day {day} part {part} is either solved or locked"
+        )),
+    }
+}
+
+fn run_bench(
+    registry: &Registry,
+    day: Option<u8>,
+    runs: usize,
+    output: Option<&Path>,
+    baseline: Option<&Path>,
+    source: &InputSource,
+) -> Result<(), String> {
+    let baseline = baseline
+        .map(|path| {
+            Report::load(path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+        })
+        .transpose()?;
+    let days = day.map(|day| vec![day]).unwrap_or_default();
+    let report = bench(registry, source, &days, runs);
+    let ms = |nanos: u64| format!("{:.3}ms", nanos as f64 / 1_000_000.0);
+    println!(
+        "{:>3} | {:>4} | {:>10} | {:>10} | {:>10} | {:>10} | {:>9} | {:>9}",
+        "day", "part", "parse", "solve", "min", "max", "allocs", "bytes"
+    );
+    for part in &report.parts {
+        if let Some(e) = &part.error {
+            println!("{:>3} | {:>4} | {e}", part.day, part.part);
+            continue;
+        }
+        let (parse, solve) = (&part.parse, &part.solve);
+        println!(
+            "{:>3} | {:>4} | {:>10} | {:>10} | {:>10} | {:>10} | {:>9} | {:>9}",
+            part.day,
+            part.part,
+            ms(parse.nanos.median),
+            ms(solve.nanos.median),
+            ms(parse.nanos.min + solve.nanos.min),
+            ms(parse.nanos.max + solve.nanos.max),
+            parse.allocations.median + solve.allocations.median,
+            parse.allocated_bytes.median + solve.allocated_bytes.median,
+        );
+    }
+    if let Some(baseline) = &baseline {
+        println!();
+        for change in report.compare(baseline) {
+            println!("{change}");
+        }
+    }
+    if let Some(path) = output {
+        report
+            .save(path)
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let registry = Registry::default();
+    let res = match command {
+        Command::Run {
+            day,
+            part,
+            input,
+            inputs,
+        } => {
+            let parts = part.map(|p| vec![p]).unwrap_or_else(|| Part::ALL.to_vec());
+            single_input_source(day, input, inputs)
+                .and_then(|source| run(&registry, day, &parts, &source))
+        }
+        Command::RunAll { inputs } => input_source(inputs)
+            .map_err(|e| e.to_string())
+            .and_then(|source| run_all(&registry, &source)),
+        Command::Fetch { day, inputs } => input_source(inputs)
+            .map_err(|e| e.to_string())
+            .and_then(|source| fetch(day, &source)),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            inputs,
+        } => input_source(inputs)
+            .map_err(|e| e.to_string())
+            .and_then(|source| submit(&registry, day, part, answer, &source)),
+        Command::Bench {
+            day,
+            runs,
+            output,
+            baseline,
+            inputs,
+        } => input_source(inputs)
+            .map_err(|e| e.to_string())
+            .and_then(|source| {
+                run_bench(
+                    &registry,
+                    day,
+                    runs,
+                    output.as_deref(),
+                    baseline.as_deref(),
+                    &source,
+                )
+            }),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_args, Command, Input, Inputs};
+    use advent_of_code::solver::Part;
+    use std::path::PathBuf;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_single_part() {
+        let command = parse_args(args("run --day 9 --part 2 --input -"));
+        assert_eq!(
+            Ok(Command::Run {
+                day: 9,
+                part: Some(Part::Two),
+                input: Some(Input::Stdin),
+                inputs: Inputs::default(),
+            }),
+            command
+        );
+    }
+
+    #[test]
+    fn parses_input_path() {
+        let command = parse_args(args("run --input /tmp/day_1.txt --day 1"));
+        assert_eq!(
+            Ok(Command::Run {
+                day: 1,
+                part: None,
+                input: Some(Input::File(PathBuf::from("/tmp/day_1.txt"))),
+                inputs: Inputs::default(),
+            }),
+            command
+        );
+    }
+
+    #[test]
+    fn parses_run_all() {
+        let command = parse_args(args("run --all --inputs somewhere --user alice"));
+        assert_eq!(
+            Ok(Command::RunAll {
+                inputs: Inputs {
+                    dir: Some(PathBuf::from("somewhere")),
+                    user: Some("alice".to_string()),
+                }
+            }),
+            command
+        );
+    }
+
+    #[test]
+    fn parses_fetch() {
+        let command = parse_args(args("fetch --day 14 --user bob"));
+        assert_eq!(
+            Ok(Command::Fetch {
+                day: 14,
+                inputs: Inputs {
+                    dir: None,
+                    user: Some("bob".to_string()),
+                }
+            }),
+            command
+        );
+    }
+
+    #[test]
+    fn parses_submit() {
+        let command = parse_args(args("submit --day 9 --part 2 --answer 2653"));
+        assert_eq!(
+            Ok(Command::Submit {
+                day: 9,
+                part: Part::Two,
+                answer: Some("2653".to_string()),
+                inputs: Inputs::default(),
+            }),
+            command
+        );
+    }
+
+    #[test]
+    fn parses_bench() {
+        let command = parse_args(args("bench --runs 5 --output target/bench.json"));
+        assert_eq!(
+            Ok(Command::Bench {
+                day: None,
+                runs: 5,
+                output: Some(PathBuf::from("target/bench.json")),
+                baseline: None,
+                inputs: Inputs::default(),
+            }),
+            command
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_args() {
+        assert!(parse_args(args("run")).is_err());
+        assert!(parse_args(args("walk --day 1")).is_err());
+        assert!(parse_args(args("run --day 1 --part 3")).is_err());
+        assert!(parse_args(args("run --day")).is_err());
+        assert!(parse_args(args("run --all --day 1")).is_err());
+        assert!(parse_args(args("fetch --all")).is_err());
+        assert!(parse_args(args("fetch --day 1 --part 1")).is_err());
+        assert!(parse_args(args("submit --day 1")).is_err());
+        assert!(parse_args(args("run --day 1 --answer 42")).is_err());
+        assert!(parse_args(args("run --all --runs 3")).is_err());
+        assert!(parse_args(args("bench --runs 0")).is_err());
+    }
+}
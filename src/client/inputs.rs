@@ -0,0 +1,79 @@
+use crate::client::{store, Client, ClientError};
+use crate::utils::io::{InputError, InputSource};
+
+impl Client {
+    /// The input of `day`, downloaded only when `cache` doesn't have it yet
+    pub fn input(&mut self, day: u8, cache: &InputSource) -> Result<String, ClientError> {
+        match cache.read(day) {
+            Ok(content) => return Ok(content),
+            Err(InputError::NotFound { .. }) => {}
+            Err(e) => return Err(e.into()),
+        }
+        let content = self.get(&format!("{}/input", self.day_url(day)))?;
+        if let Some(path) = cache.path(day) {
+            store(&path, &content)?;
+        }
+        Ok(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::tests::serve;
+    use crate::client::{Client, ClientError, Session};
+    use crate::utils::io::InputSource;
+    use std::fs;
+    use std::time::Duration;
+
+    fn client(url: &str) -> Client {
+        Client::with_base_url(Session::new("s3cr3t"), url, Duration::ZERO)
+    }
+
+    #[test]
+    fn downloads_then_caches() {
+        let dir = std::env::temp_dir().join(format!("aoc_client_cache_{}", std::process::id()));
+        let cache = InputSource::UserDirectory {
+            root: dir.clone(),
+            user: "alice".to_string(),
+        };
+        let (url, server) = serve(vec![(200, "1000\n2000\n")]);
+        let mut client = client(&url);
+        assert_eq!("1000\n2000\n", client.input(1, &cache).unwrap());
+        assert_eq!(
+            "1000\n2000\n",
+            fs::read_to_string(dir.join("alice").join("day_1.txt")).unwrap()
+        );
+        // second call is served from the cache, the server would hang otherwise
+        assert_eq!("1000\n2000\n", client.input(1, &cache).unwrap());
+
+        let requests = server.join().unwrap();
+        assert_eq!(1, requests.len());
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/2022/day/1/input", requests[0].path);
+        assert_eq!(Some("session=s3cr3t"), requests[0].header("Cookie"));
+        assert_eq!("", requests[0].body);
+        assert!(requests[0]
+            .header("User-Agent")
+            .unwrap()
+            .contains("advent-of-code-22"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn memory_inputs_are_not_downloaded_again() {
+        let cache = InputSource::in_memory(3, "vJrwpWtwJgWr");
+        let mut client = client("http://127.0.0.1:9");
+        assert_eq!("vJrwpWtwJgWr", client.input(3, &cache).unwrap());
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let dir = std::env::temp_dir().join(format!("aoc_client_errors_{}", std::process::id()));
+        let cache = InputSource::Directory(dir.clone());
+        let (url, server) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let res = client(&url).input(2, &cache);
+        assert!(matches!(res, Err(ClientError::Status { status: 400, .. })));
+        server.join().unwrap();
+        assert!(!dir.join("day_2.txt").exists());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::client::{store, ClientError};
+use crate::solver::Part;
+use crate::utils::io::InputSource;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fmt, fs, io};
+
+pub const LEDGER_FILE: &str = "answers.json";
+
+/// What adventofcode.com tells about a wrong answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejection {
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<Rejection>,
+    /// Unix timestamp (seconds) before which no answer may be sent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown_until: Option<u64>,
+}
+
+impl PartRecord {
+    fn is_empty(&self) -> bool {
+        self == &PartRecord::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DayRecord {
+    #[serde(default, skip_serializing_if = "PartRecord::is_empty")]
+    part_1: PartRecord,
+    #[serde(default, skip_serializing_if = "PartRecord::is_empty")]
+    part_2: PartRecord,
+}
+
+impl DayRecord {
+    fn part(&self, part: Part) -> &PartRecord {
+        match part {
+            Part::One => &self.part_1,
+            Part::Two => &self.part_2,
+        }
+    }
+
+    fn part_mut(&mut self, part: Part) -> &mut PartRecord {
+        match part {
+            Part::One => &mut self.part_1,
+            Part::Two => &mut self.part_2,
+        }
+    }
+}
+
+/// Why an answer is not worth sending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// Another answer has already been accepted for this part
+    AlreadyAccepted(String),
+    KnownWrong(Rejection),
+    /// A rejected answer was said too high (or too low) and this one is even higher (lower)
+    OutOfRange(Rejection),
+    CoolingDown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyAccepted(answer) => write!(f, "{answer} has already been accepted"),
+            Refusal::KnownWrong(rejection) => {
+                write!(f, "{} has already been rejected", rejection.answer)
+            }
+            Refusal::OutOfRange(Rejection { answer, hint }) => match hint {
+                Some(Hint::TooLow) => write!(f, "{answer} was already too low"),
+                _ => write!(f, "{answer} was already too high"),
+            },
+            Refusal::CoolingDown(wait) => {
+                write!(f, "wait {}s before submitting again", wait.as_secs())
+            }
+        }
+    }
+}
+
+/// Answers submitted so far, by day and part, persisted as JSON next to the inputs
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    path: Option<PathBuf>,
+    days: BTreeMap<u8, DayRecord>,
+}
+
+impl Ledger {
+    /// `<inputs>/answers.json`, kept in memory only for in-memory inputs
+    pub fn for_inputs(source: &InputSource) -> Result<Self, ClientError> {
+        let path = match source {
+            InputSource::Directory(dir) => Some(dir.join(LEDGER_FILE)),
+            InputSource::UserDirectory { root, user } => Some(root.join(user).join(LEDGER_FILE)),
+            InputSource::File(path) => path.parent().map(|dir| dir.join(LEDGER_FILE)),
+            InputSource::Memory(_) => None,
+        };
+        match path {
+            Some(path) => Self::load(path),
+            None => Ok(Ledger::default()),
+        }
+    }
+
+    /// A missing file is an empty ledger, created on first `save`
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ClientError> {
+        let path = path.into();
+        let days = match fs::read_to_string(&path) {
+            Ok(content) => {
+                serde_json::from_str(&content).map_err(|e| ClientError::InvalidLedger {
+                    path: path.clone(),
+                    reason: e.to_string(),
+                })?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Ledger {
+            path: Some(path),
+            days,
+        })
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn save(&self) -> Result<(), ClientError> {
+        match &self.path {
+            Some(path) => {
+                let content = serde_json::to_string_pretty(&self.days).map_err(|e| {
+                    ClientError::InvalidLedger {
+                        path: path.clone(),
+                        reason: e.to_string(),
+                    }
+                })?;
+                store(path, &format!("{content}\n"))
+            }
+            None => Ok(()),
+        }
+    }
+
+    pub fn record(&self, day: u8, part: Part) -> Option<&PartRecord> {
+        self.days.get(&day).map(|record| record.part(part))
+    }
+
+    pub fn accepted(&self, day: u8, part: Part) -> Option<&str> {
+        self.record(day, part)?.accepted.as_deref()
+    }
+
+    /// Every accepted answer, ordered by day then part
+    pub fn verified(&self) -> impl Iterator<Item = (u8, Part, &str)> {
+        self.days.iter().flat_map(|(day, record)| {
+            Part::ALL.into_iter().filter_map(move |part| {
+                record
+                    .part(part)
+                    .accepted
+                    .as_deref()
+                    .map(|answer| (*day, part, answer))
+            })
+        })
+    }
+
+    /// `Ok` when `answer` may be sent at `now` (unix seconds)
+    pub fn check(&self, day: u8, part: Part, answer: &str, now: u64) -> Result<(), Refusal> {
+        let record = match self.record(day, part) {
+            Some(record) => record,
+            None => return Ok(()),
+        };
+        if let Some(accepted) = &record.accepted {
+            return Err(Refusal::AlreadyAccepted(accepted.clone()));
+        }
+        if let Some(rejection) = record.rejected.iter().find(|r| r.answer == answer) {
+            return Err(Refusal::KnownWrong(rejection.clone()));
+        }
+        if let Ok(value) = answer.parse::<i64>() {
+            let out_of_range = record.rejected.iter().find(|r| {
+                let rejected = match r.answer.parse::<i64>() {
+                    Ok(rejected) => rejected,
+                    Err(_) => return false,
+                };
+                match r.hint {
+                    Some(Hint::TooHigh) => value > rejected,
+                    Some(Hint::TooLow) => value < rejected,
+                    None => false,
+                }
+            });
+            if let Some(rejection) = out_of_range {
+                return Err(Refusal::OutOfRange(rejection.clone()));
+            }
+        }
+        match record.cooldown_until {
+            Some(until) if until > now => {
+                Err(Refusal::CoolingDown(Duration::from_secs(until - now)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn accept(&mut self, day: u8, part: Part, answer: &str) {
+        let record = self.days.entry(day).or_default().part_mut(part);
+        record.accepted = Some(answer.to_string());
+        record.cooldown_until = None;
+    }
+
+    pub(crate) fn reject(&mut self, day: u8, part: Part, rejection: Rejection) {
+        let record = self.days.entry(day).or_default().part_mut(part);
+        if !record.rejected.contains(&rejection) {
+            record.rejected.push(rejection);
+        }
+    }
+
+    pub(crate) fn cool_down(&mut self, day: u8, part: Part, until: u64) {
+        self.days
+            .entry(day)
+            .or_default()
+            .part_mut(part)
+            .cooldown_until = Some(until);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::ledger::{Hint, Ledger, Refusal, Rejection};
+    use crate::solver::Part;
+    use crate::utils::io::website_inputs;
+    use std::fs;
+    use std::time::Duration;
+
+    fn rejection(answer: &str, hint: Option<Hint>) -> Rejection {
+        Rejection {
+            answer: answer.to_string(),
+            hint,
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut ledger = Ledger::default();
+        assert_eq!(Ok(()), ledger.check(1, Part::One, "100", 0));
+        ledger.reject(1, Part::One, rejection("100", Some(Hint::TooHigh)));
+        ledger.reject(1, Part::One, rejection("10", Some(Hint::TooLow)));
+        ledger.reject(1, Part::One, rejection("42", None));
+        assert!(matches!(
+            ledger.check(1, Part::One, "42", 0),
+            Err(Refusal::KnownWrong(_))
+        ));
+        assert_eq!(
+            Err(Refusal::OutOfRange(rejection("100", Some(Hint::TooHigh)))),
+            ledger.check(1, Part::One, "150", 0)
+        );
+        assert_eq!(
+            Err(Refusal::OutOfRange(rejection("10", Some(Hint::TooLow)))),
+            ledger.check(1, Part::One, "3", 0)
+        );
+        assert_eq!(Ok(()), ledger.check(1, Part::One, "50", 0));
+        assert_eq!(Ok(()), ledger.check(1, Part::Two, "42", 0));
+    }
+
+    #[test]
+    fn enforces_cooldown() {
+        let mut ledger = Ledger::default();
+        ledger.cool_down(3, Part::Two, 1_060);
+        assert_eq!(
+            Err(Refusal::CoolingDown(Duration::from_secs(60))),
+            ledger.check(3, Part::Two, "7766", 1_000)
+        );
+        assert_eq!(Ok(()), ledger.check(3, Part::Two, "7766", 1_060));
+    }
+
+    #[test]
+    fn nothing_to_send_once_accepted() {
+        let mut ledger = Ledger::default();
+        ledger.accept(9, Part::Two, "2653");
+        assert_eq!(Some("2653"), ledger.accepted(9, Part::Two));
+        assert_eq!(
+            Err(Refusal::AlreadyAccepted("2653".to_string())),
+            ledger.check(9, Part::Two, "2654", 0)
+        );
+    }
+
+    #[test]
+    fn persists_as_json() {
+        let dir = std::env::temp_dir().join(format!("aoc_ledger_{}", std::process::id()));
+        let path = dir.join("answers.json");
+        let mut ledger = Ledger::load(&path).unwrap();
+        ledger.accept(11, Part::Two, "24389045529");
+        ledger.reject(11, Part::One, rejection("56000", Some(Hint::TooLow)));
+        ledger.save().unwrap();
+
+        let reloaded = Ledger::load(&path).unwrap();
+        assert_eq!(Some("24389045529"), reloaded.accepted(11, Part::Two));
+        assert_eq!(
+            vec![rejection("56000", Some(Hint::TooLow))],
+            reloaded.record(11, Part::One).unwrap().rejected
+        );
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"too_low\""));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reads_website_inputs_ledger() {
+        let ledger = Ledger::for_inputs(&website_inputs()).unwrap();
+        assert_eq!(Some("24389045529"), ledger.accepted(11, Part::Two));
+        assert!(ledger.verified().count() > 20);
+    }
+}
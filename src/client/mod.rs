@@ -0,0 +1,336 @@
+use crate::utils::io::{load_config, InputError};
+use std::fmt::{Debug, Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use std::{env, fmt, fs, io, thread};
+
+mod inputs;
+mod ledger;
+mod submit;
+
+pub use ledger::{Hint, Ledger, PartRecord, Refusal, Rejection, LEDGER_FILE};
+pub use submit::Verdict;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+/// Session cookie of the adventofcode.com account, overriding the `session` of the config file
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// adventofcode.com asks automated tools to identify themselves and to throttle their requests
+const USER_AGENT: &str = "github.com/aesteve/advent-of-code-22";
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Value of the `session` cookie, kept out of `Debug` output
+#[derive(Clone, PartialEq, Eq)]
+pub struct Session(String);
+
+impl Session {
+    pub fn new(token: impl Into<String>) -> Self {
+        Session(token.into().trim().to_string())
+    }
+
+    /// From `$AOC_SESSION`, then the `session` entry of the config file
+    pub fn from_env() -> Result<Self, ClientError> {
+        Self::resolve(|key| env::var(key).ok())
+    }
+
+    fn resolve(var: impl Fn(&str) -> Option<String>) -> Result<Self, ClientError> {
+        let token = match var(SESSION_ENV) {
+            Some(token) => Some(token),
+            None => load_config(&var).map_err(ClientError::Input)?.session,
+        };
+        token
+            .filter(|token| !token.trim().is_empty())
+            .map(Session::new)
+            .ok_or(ClientError::NoSession)
+    }
+}
+
+impl Debug for Session {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Session(***)")
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// Neither `$AOC_SESSION` nor the config file provide a session cookie
+    NoSession,
+    /// adventofcode.com answered with an unexpected status (expired session, locked puzzle, ...)
+    Status {
+        status: u16,
+        body: String,
+    },
+    /// The request could not be sent or its response read
+    Transport(String),
+    /// The answer page didn't look like anything we know of
+    Unrecognized(String),
+    /// The ledger knows better than sending this answer
+    Refused(Refusal),
+    InvalidLedger {
+        path: PathBuf,
+        reason: String,
+    },
+    Input(InputError),
+    Io(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session cookie, set ${SESSION_ENV} or `session` in the config file"
+            ),
+            ClientError::Status { status, body } => {
+                write!(f, "unexpected HTTP status {status}: {}", body.trim())
+            }
+            ClientError::Transport(reason) => write!(f, "request failed: {reason}"),
+            ClientError::Unrecognized(_) => write!(f, "unrecognized answer page"),
+            ClientError::Refused(refusal) => write!(f, "not submitted, {refusal}"),
+            ClientError::InvalidLedger { path, reason } => {
+                write!(f, "invalid answer ledger {}: {reason}", path.display())
+            }
+            ClientError::Input(e) => write!(f, "{e}"),
+            ClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<InputError> for ClientError {
+    fn from(value: InputError) -> Self {
+        ClientError::Input(value)
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(value: io::Error) -> Self {
+        ClientError::Io(value)
+    }
+}
+
+/// Makes sure two requests are at least `min_interval` apart
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(min_interval: Duration) -> Self {
+        RateLimiter {
+            min_interval,
+            last_request: None,
+        }
+    }
+
+    pub(crate) fn wait(&mut self) {
+        if let Some(last) = self.last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+/// Talks to adventofcode.com (or anything answering the same way) on behalf of a session
+pub struct Client {
+    base_url: String,
+    year: u16,
+    session: Session,
+    agent: ureq::Agent,
+    limiter: RateLimiter,
+}
+
+impl Client {
+    pub fn new(session: Session) -> Self {
+        Self::with_base_url(session, BASE_URL, DEFAULT_MIN_INTERVAL)
+    }
+
+    pub fn with_base_url(session: Session, base_url: &str, min_interval: Duration) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            year: YEAR,
+            session,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            limiter: RateLimiter::new(min_interval),
+        }
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{day}", self.base_url, self.year)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session.0)
+    }
+
+    fn body(res: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+        match res {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| ClientError::Transport(e.to_string())),
+            Err(ureq::Error::Status(status, response)) => Err(ClientError::Status {
+                status,
+                body: response.into_string().unwrap_or_default(),
+            }),
+            Err(ureq::Error::Transport(e)) => Err(ClientError::Transport(e.to_string())),
+        }
+    }
+
+    pub(crate) fn get(&mut self, url: &str) -> Result<String, ClientError> {
+        self.limiter.wait();
+        let res = self.agent.get(url).set("Cookie", &self.cookie()).call();
+        Self::body(res)
+    }
+
+    pub(crate) fn post_form(
+        &mut self,
+        url: &str,
+        form: &[(&str, &str)],
+    ) -> Result<String, ClientError> {
+        self.limiter.wait();
+        let res = self
+            .agent
+            .post(url)
+            .set("Cookie", &self.cookie())
+            .send_form(form);
+        Self::body(res)
+    }
+}
+
+/// Written aside then renamed, so that an interrupted write never leaves a truncated file behind
+pub(crate) fn store(path: &Path, content: &str) -> Result<(), ClientError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".part");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::client::{ClientError, RateLimiter, Session, SESSION_ENV};
+    use crate::utils::io::CONFIG_ENV;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+    use std::time::{Duration, Instant};
+    use std::{fs, thread};
+
+    /// What the stand-in server received
+    #[derive(Debug, Clone)]
+    pub(crate) struct Request {
+        pub(crate) method: String,
+        pub(crate) path: String,
+        pub(crate) headers: Vec<(String, String)>,
+        pub(crate) body: String,
+    }
+
+    impl Request {
+        pub(crate) fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Local stand-in for adventofcode.com, answering `responses` in order, one per connection
+    pub(crate) fn serve(responses: Vec<(u16, &str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let responses = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.to_string()))
+            .collect::<Vec<_>>();
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+                let mut headers = vec![];
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((key, value)) = line.split_once(':') {
+                        headers.push((key.trim().to_string(), value.trim().to_string()));
+                    }
+                }
+                let length = headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                    .and_then(|(_, value)| value.parse::<usize>().ok())
+                    .unwrap_or(0);
+                let mut body_bytes = vec![0; length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                requests.push(Request {
+                    method,
+                    path,
+                    headers,
+                    body: String::from_utf8(body_bytes).unwrap(),
+                });
+                let response = format!(
+                    "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn rate_limiter_spaces_requests() {
+        let mut limiter = RateLimiter::new(Duration::from_millis(100));
+        let start = Instant::now();
+        limiter.wait();
+        assert!(start.elapsed() < Duration::from_millis(100));
+        limiter.wait();
+        limiter.wait();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn session_is_not_debug_printed() {
+        let session = Session::new("53616c7465645f5f");
+        assert!(!format!("{session:?}").contains("5361"));
+    }
+
+    #[test]
+    fn session_from_env_or_config() {
+        let session = Session::resolve(|key| (key == SESSION_ENV).then(|| "abc\n".to_string()));
+        assert_eq!(Session::new("abc"), session.unwrap());
+
+        let dir = std::env::temp_dir().join(format!("aoc_session_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("config.json");
+        fs::write(&config, r#"{"session": "def"}"#).unwrap();
+        let config = config.to_str().unwrap().to_string();
+        let session = Session::resolve(|key| (key == CONFIG_ENV).then(|| config.clone()));
+        assert_eq!(Session::new("def"), session.unwrap());
+        fs::remove_dir_all(dir).unwrap();
+
+        let session = Session::resolve(|_| None);
+        assert!(matches!(session, Err(ClientError::NoSession)));
+    }
+}
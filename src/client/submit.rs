@@ -0,0 +1,223 @@
+use crate::client::ledger::{Hint, Ledger, Rejection};
+use crate::client::{Client, ClientError};
+use crate::solver::Part;
+use regex::Regex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How adventofcode.com received an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect {
+        hint: Option<Hint>,
+        /// Before another answer can be sent
+        wait: Option<Duration>,
+    },
+    /// The answer was not even looked at, another one has been sent too recently
+    TooSoon(Duration),
+    /// The part is either already solved or still locked
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Reads the `<article>` adventofcode.com answers with
+    pub(crate) fn from_html(body: &str) -> Option<Self> {
+        if body.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if body.contains("That's not the right answer") {
+            let hint = if body.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if body.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            let minutes = Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap();
+            let wait = minutes.captures(body).map(|capts| {
+                let minutes = capts[1].parse::<u64>().unwrap_or(1);
+                Duration::from_secs(minutes * 60)
+            });
+            Some(Verdict::Incorrect { hint, wait })
+        } else if body.contains("You gave an answer too recently") {
+            let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+            let wait = left
+                .captures(body)
+                .map(|capts| {
+                    let minutes = capts
+                        .get(1)
+                        .map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+                    let seconds = capts[2].parse::<u64>().unwrap();
+                    minutes * 60 + seconds
+                })
+                .unwrap_or(60);
+            Some(Verdict::TooSoon(Duration::from_secs(wait)))
+        } else if body.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+impl Client {
+    /// Sends `answer` unless `ledger` already knows how it would be received, then records the verdict
+    pub fn submit(
+        &mut self,
+        day: u8,
+        part: Part,
+        answer: &str,
+        ledger: &mut Ledger,
+    ) -> Result<Verdict, ClientError> {
+        let answer = answer.trim();
+        if ledger.accepted(day, part) == Some(answer) {
+            return Ok(Verdict::Correct);
+        }
+        ledger
+            .check(day, part, answer, unix_now())
+            .map_err(ClientError::Refused)?;
+        let level = match part {
+            Part::One => "1",
+            Part::Two => "2",
+        };
+        let url = format!("{}/answer", self.day_url(day));
+        let body = self.post_form(&url, &[("level", level), ("answer", answer)])?;
+        let verdict = Verdict::from_html(&body).ok_or(ClientError::Unrecognized(body))?;
+        match &verdict {
+            Verdict::Correct => ledger.accept(day, part, answer),
+            Verdict::Incorrect { hint, wait } => {
+                ledger.reject(
+                    day,
+                    part,
+                    Rejection {
+                        answer: answer.to_string(),
+                        hint: *hint,
+                    },
+                );
+                if let Some(wait) = wait {
+                    ledger.cool_down(day, part, unix_now() + wait.as_secs());
+                }
+            }
+            Verdict::TooSoon(wait) => ledger.cool_down(day, part, unix_now() + wait.as_secs()),
+            Verdict::WrongLevel => return Ok(verdict),
+        }
+        ledger.save()?;
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::ledger::{Hint, Ledger, Refusal};
+    use crate::client::submit::Verdict;
+    use crate::client::tests::serve;
+    use crate::client::{Client, ClientError, Session};
+    use crate::solver::Part;
+    use std::time::Duration;
+
+    const RIGHT: &str =
+        "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        Please wait one minute before trying again.</p></article>";
+    const TOO_RECENTLY: &str = "<article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>";
+
+    fn client(url: &str) -> Client {
+        Client::with_base_url(Session::new("s3cr3t"), url, Duration::ZERO)
+    }
+
+    #[test]
+    fn reads_verdicts() {
+        assert_eq!(Some(Verdict::Correct), Verdict::from_html(RIGHT));
+        assert_eq!(
+            Some(Verdict::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }),
+            Verdict::from_html(TOO_HIGH)
+        );
+        assert_eq!(
+            Some(Verdict::TooSoon(Duration::from_secs(65))),
+            Verdict::from_html(TOO_RECENTLY)
+        );
+        assert_eq!(None, Verdict::from_html("<html>Oops</html>"));
+    }
+
+    #[test]
+    fn submits_and_records() {
+        let (url, server) = serve(vec![(200, RIGHT)]);
+        let mut ledger = Ledger::default();
+        let verdict = client(&url).submit(9, Part::Two, "2653\n", &mut ledger);
+        assert_eq!(Verdict::Correct, verdict.unwrap());
+        assert_eq!(Some("2653"), ledger.accepted(9, Part::Two));
+
+        let requests = server.join().unwrap();
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("/2022/day/9/answer", requests[0].path);
+        assert_eq!("level=2&answer=2653", requests[0].body);
+        assert_eq!(Some("session=s3cr3t"), requests[0].header("Cookie"));
+    }
+
+    #[test]
+    fn does_not_resend_wrong_answers() {
+        let (url, server) = serve(vec![(200, TOO_HIGH)]);
+        let mut ledger = Ledger::default();
+        let mut client = client(&url);
+        let verdict = client.submit(1, Part::One, "70000", &mut ledger).unwrap();
+        assert!(matches!(verdict, Verdict::Incorrect { .. }));
+        assert_eq!(1, server.join().unwrap().len());
+
+        // neither the same answer nor a higher one reach the server, which is gone anyway
+        let res = client.submit(1, Part::One, "70000", &mut ledger);
+        assert!(matches!(
+            res,
+            Err(ClientError::Refused(Refusal::KnownWrong(_)))
+        ));
+        let res = client.submit(1, Part::One, "80000", &mut ledger);
+        assert!(matches!(
+            res,
+            Err(ClientError::Refused(Refusal::OutOfRange(_)))
+        ));
+        // and the one minute penalty applies to any other answer
+        let res = client.submit(1, Part::One, "67450", &mut ledger);
+        assert!(matches!(
+            res,
+            Err(ClientError::Refused(Refusal::CoolingDown(_)))
+        ));
+    }
+
+    #[test]
+    fn respects_server_cooldown() {
+        let (url, server) = serve(vec![(200, TOO_RECENTLY)]);
+        let mut ledger = Ledger::default();
+        let mut client = client(&url);
+        let verdict = client.submit(2, Part::One, "15572", &mut ledger).unwrap();
+        assert_eq!(Verdict::TooSoon(Duration::from_secs(65)), verdict);
+        server.join().unwrap();
+        let res = client.submit(2, Part::One, "15572", &mut ledger);
+        assert!(matches!(
+            res,
+            Err(ClientError::Refused(Refusal::CoolingDown(_)))
+        ));
+    }
+
+    #[test]
+    fn accepted_answers_are_not_sent_again() {
+        let mut ledger = Ledger::default();
+        ledger.accept(4, Part::One, "580");
+        let mut client = client("http://127.0.0.1:9");
+        assert_eq!(
+            Verdict::Correct,
+            client.submit(4, Part::One, "580", &mut ledger).unwrap()
+        );
+        assert!(matches!(
+            client.submit(4, Part::One, "581", &mut ledger),
+            Err(ClientError::Refused(Refusal::AlreadyAccepted(_)))
+        ));
+    }
+}
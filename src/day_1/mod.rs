@@ -1,3 +1,7 @@
+use crate::day_1::part_1::most_calories_carried;
+use crate::day_1::part_2::three_most_calories_carried;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+use std::convert::identity;
 use std::str::FromStr;
 
 mod part_1;
@@ -8,6 +12,33 @@ pub(crate) fn cals_from_line(line: std::io::Result<String>) -> Option<u32> {
     line.ok().and_then(|str| u32::from_str(&str).ok())
 }
 
+pub(crate) struct Day1;
+
+impl Solver for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<Option<u32>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| {
+            if line.is_empty() {
+                Ok(None)
+            } else {
+                u32::from_str(line).map(Some)
+            }
+        })
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(most_calories_carried(input.iter().copied(), identity))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(three_most_calories_carried(input.iter().copied(), identity))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils::io::{input_file_lines, FileLines};
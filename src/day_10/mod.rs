@@ -1,78 +1,40 @@
 mod part_1;
+mod part_2;
+mod vm;
 
-#[derive(Debug)]
-pub(crate) struct CpuState {
-    cycle: u64,
-    register: i64,
-}
+use crate::day_10::part_1::signal_strength;
+use crate::day_10::part_2::Crt;
+use crate::day_10::vm::Instruction;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+use std::str::FromStr;
 
-#[derive(Debug)]
-pub(crate) enum Instruction {
-    Noop,
-    Addx(i64),
-}
+pub(crate) struct Day10;
 
-impl Default for CpuState {
-    fn default() -> Self {
-        CpuState {
-            cycle: 0,
-            register: 1,
-        }
-    }
-}
+impl Solver for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = String;
 
-impl CpuState {
-    pub(crate) fn exec(&mut self, instruction: &Instruction) -> Option<(u64, i64)> {
-        let before_value = self.register;
-        let passed_threshold = match instruction {
-            Instruction::Noop => self.cycle_through(),
-            Instruction::Addx(value) => {
-                let passed = self.cycle_through();
-                self.cycle_through();
-                self.register += *value;
-                passed
-            }
-        };
-        if let Some(passed) = passed_threshold {
-            Some((passed, before_value))
-        } else if let Some(threshold) = self.at_threshold() {
-            Some((threshold, self.register))
-        } else {
-            None
-        }
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, Instruction::from_str)
     }
 
-    fn cycle_through(&mut self) -> Option<u64> {
-        self.cycle += 1;
-        self.at_threshold()
+    fn part_1(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        Ok(signal_strength(input))
     }
 
-    fn at_threshold(&self) -> Option<u64> {
-        if self.cycle >= 20 && (self.cycle - 20) % 40 == 0 {
-            Some(self.cycle)
-        } else {
-            None
-        }
-    }
-}
-
-impl From<&str> for Instruction {
-    fn from(value: &str) -> Self {
-        match value {
-            "noop" => Instruction::Noop,
-            _ => {
-                let mut splitted = value.split(' ');
-                splitted.next();
-                let qty = splitted.next().unwrap();
-                Instruction::Addx(qty.parse().unwrap())
-            }
-        }
+    fn part_2(&self, input: &Self::Input) -> Result<String, SolveError> {
+        let crt = Crt::draw(input);
+        crt.read()
+            .map_err(|e| SolveError::NoSolution(format!("{e}, the screen reads:\n{crt}")))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_10::{CpuState, Instruction};
+    use crate::day_10::part_1::signal_strength;
+    use crate::day_10::vm::Instruction;
     use crate::utils::io::{input_file_lines, FileLines};
 
     pub(crate) fn sample() -> Vec<Instruction> {
@@ -96,7 +58,7 @@ mod tests {
             "addx 1", "addx 2", "addx 2", "addx -6", "addx -11", "noop", "noop", "noop",
         ]
         .into_iter()
-        .map(Instruction::from)
+        .map(|line| line.parse().unwrap())
         .collect()
     }
 
@@ -106,13 +68,6 @@ mod tests {
 
     #[test]
     fn check_sample() {
-        let mut state = CpuState::default();
-        let mut sum = 0;
-        for instruction in sample() {
-            if let Some((cycle, res)) = state.exec(&instruction) {
-                sum += res * cycle as i64;
-            }
-        }
-        assert_eq!(13360, sum);
+        assert_eq!(13140, signal_strength(&sample()));
     }
 }
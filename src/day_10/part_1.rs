@@ -1,19 +1,31 @@
+use crate::day_10::vm::{Instruction, Vm};
+
+/// Cycles during which the signal is sampled: the 20th then every 40th
+fn sampled(cycle: u64) -> bool {
+    cycle >= 20 && (cycle - 20).is_multiple_of(40)
+}
+
+pub(crate) fn signal_strength(instructions: &[Instruction]) -> i64 {
+    let mut sum = 0;
+    Vm::handheld(instructions).run(|tick| {
+        if sampled(tick.cycle) {
+            sum += tick.cycle as i64 * tick.registers.get('x');
+        }
+    });
+    sum
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::day_10::part_1::signal_strength;
     use crate::day_10::tests::puzzle_input;
-    use crate::day_10::{CpuState, Instruction};
+    use crate::day_10::vm::Instruction;
 
     #[test]
     fn solution() {
-        let mut state = CpuState::default();
-        let lines = puzzle_input();
-        let mut sum = 0;
-        for line in lines {
-            let instruction = Instruction::from(line.unwrap().as_str());
-            if let Some((cycle, res)) = state.exec(&instruction) {
-                sum += res * cycle as i64;
-            }
-        }
-        assert_eq!(15260, sum);
+        let instructions = puzzle_input()
+            .map(|line| line.unwrap().parse::<Instruction>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(15260, signal_strength(&instructions));
     }
 }
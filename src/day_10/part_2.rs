@@ -0,0 +1,121 @@
+use crate::day_10::vm::{Instruction, Vm};
+use crate::utils::geom::Coord;
+use crate::utils::grid::Grid;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
+/// Letters are 4 pixels wide, followed by an empty column
+const GLYPH_WIDTH: usize = 5;
+const LIT: char = '#';
+const DARK: char = '.';
+
+/// The capital letters the CRT is known to draw, row by row
+const FONT: [(char, &str); 18] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Y', "#...#....#.#..#...#...#."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// The 40x6 screen, one pixel drawn per cycle, lit when the 3 pixels wide sprite is over it
+pub(crate) struct Crt {
+    pixels: Grid<char>,
+}
+
+impl Crt {
+    pub(crate) fn draw(instructions: &[Instruction]) -> Self {
+        let mut pixels = Grid::filled(WIDTH, HEIGHT, DARK);
+        Vm::handheld(instructions).run(|tick| {
+            let position = (tick.cycle as usize - 1) % (WIDTH * HEIGHT);
+            let pixel = Coord::new((position % WIDTH) as i64, (position / WIDTH) as i64);
+            if (tick.registers.get('x') - pixel.x).abs() <= 1 {
+                pixels[&pixel] = LIT;
+            }
+        });
+        Crt { pixels }
+    }
+
+    fn glyph(&self, index: usize) -> String {
+        (0..HEIGHT)
+            .flat_map(|y| {
+                self.pixels
+                    .row(y)
+                    .skip(index * GLYPH_WIDTH)
+                    .take(GLYPH_WIDTH - 1)
+            })
+            .collect()
+    }
+
+    /// Recognizes the letters drawn, failing on the first one not in the font
+    pub(crate) fn read(&self) -> Result<String, String> {
+        (0..WIDTH / GLYPH_WIDTH)
+            .map(|i| {
+                let glyph = self.glyph(i);
+                FONT.iter()
+                    .find(|(_, pixels)| *pixels == glyph)
+                    .map(|(letter, _)| *letter)
+                    .ok_or(format!("Unknown letter at position {}", i + 1))
+            })
+            .collect()
+    }
+}
+
+impl Display for Crt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_10::part_2::Crt;
+    use crate::day_10::tests::{puzzle_input, sample};
+    use crate::day_10::vm::Instruction;
+
+    #[test]
+    fn check_sample() {
+        let crt = Crt::draw(&sample());
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(expected, crt.to_string());
+        assert!(crt.read().is_err());
+    }
+
+    #[test]
+    fn solution() {
+        let instructions = puzzle_input()
+            .map(|line| line.unwrap().parse::<Instruction>().unwrap())
+            .collect::<Vec<_>>();
+        let crt = Crt::draw(&instructions);
+        let expected = "\
+###...##..#..#.####..##..#....#..#..##..
+#..#.#..#.#..#.#....#..#.#....#..#.#..#.
+#..#.#....####.###..#....#....#..#.#....
+###..#.##.#..#.#....#.##.#....#..#.#.##.
+#....#..#.#..#.#....#..#.#....#..#.#..#.
+#.....###.#..#.#.....###.####..##...###.";
+        assert_eq!(expected, crt.to_string());
+        assert_eq!(Ok("PGHFGLUG".to_string()), crt.read());
+    }
+}
@@ -0,0 +1,228 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Instruction {
+    Noop,
+    /// `add<register> <value>`, `addx 3` adding 3 to `x`
+    Add {
+        register: char,
+        value: i64,
+    },
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        if line == "noop" {
+            return Ok(Instruction::Noop);
+        }
+        let unknown = || format!("Unknown instruction {line:?}");
+        let (op, value) = line.split_once(' ').ok_or_else(unknown)?;
+        let mut name = op.strip_prefix("add").ok_or_else(unknown)?.chars();
+        let register = match (name.next(), name.next()) {
+            (Some(register), None) if register.is_ascii_lowercase() => register,
+            _ => return Err(unknown()),
+        };
+        let value = value
+            .parse()
+            .map_err(|_| format!("Invalid {op} operand {value:?}"))?;
+        Ok(Instruction::Add { register, value })
+    }
+}
+
+/// Cycles each instruction takes before its effect shows, 0 being taken as 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Latency {
+    pub(crate) noop: u64,
+    pub(crate) add: u64,
+}
+
+impl Default for Latency {
+    fn default() -> Self {
+        Latency { noop: 1, add: 2 }
+    }
+}
+
+impl Latency {
+    fn of(&self, instruction: &Instruction) -> u64 {
+        let cycles = match instruction {
+            Instruction::Noop => self.noop,
+            Instruction::Add { .. } => self.add,
+        };
+        cycles.max(1)
+    }
+}
+
+/// One register per lowercase letter, all starting at 0
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Registers([i64; 26]);
+
+impl Registers {
+    fn index(name: char) -> usize {
+        assert!(name.is_ascii_lowercase(), "No register named {name:?}");
+        name as usize - 'a' as usize
+    }
+
+    pub(crate) fn get(&self, name: char) -> i64 {
+        self.0[Self::index(name)]
+    }
+
+    fn set(&mut self, name: char, value: i64) {
+        self.0[Self::index(name)] = value;
+    }
+}
+
+/// What observers are shown of a cycle: registers hold their values *during* it
+#[derive(Debug)]
+pub(crate) struct Tick<'a> {
+    /// Starting at 1
+    pub(crate) cycle: u64,
+    /// Only traced by tests, the puzzle samples registers
+    #[cfg(test)]
+    pub(crate) instruction: &'a Instruction,
+    pub(crate) registers: &'a Registers,
+}
+
+/// Runs a program one cycle at a time, an instruction's effect showing once its last cycle is over
+#[derive(Debug, Clone)]
+pub(crate) struct Vm<'a> {
+    program: &'a [Instruction],
+    latency: Latency,
+    registers: Registers,
+    cycle: u64,
+    /// Index of the instruction being executed
+    pc: usize,
+    /// Cycles already spent on it
+    spent: u64,
+}
+
+impl<'a> Vm<'a> {
+    pub(crate) fn new(program: &'a [Instruction]) -> Self {
+        Vm {
+            program,
+            latency: Latency::default(),
+            registers: Registers::default(),
+            cycle: 0,
+            pc: 0,
+            spent: 0,
+        }
+    }
+
+    /// The handheld's CPU, `x` starting at 1
+    pub(crate) fn handheld(program: &'a [Instruction]) -> Self {
+        Vm::new(program).with_register('x', 1)
+    }
+
+    pub(crate) fn with_register(mut self, name: char, value: i64) -> Self {
+        self.registers.set(name, value);
+        self
+    }
+
+    /// Runs a single cycle, `false` when there was nothing left to run
+    pub(crate) fn step(&mut self, observer: &mut impl FnMut(&Tick)) -> bool {
+        let Some(instruction) = self.program.get(self.pc) else {
+            return false;
+        };
+        self.cycle += 1;
+        observer(&Tick {
+            cycle: self.cycle,
+            #[cfg(test)]
+            instruction,
+            registers: &self.registers,
+        });
+        self.spent += 1;
+        if self.spent >= self.latency.of(instruction) {
+            if let Instruction::Add { register, value } = instruction {
+                self.registers
+                    .set(*register, self.registers.get(*register) + value);
+            }
+            self.pc += 1;
+            self.spent = 0;
+        }
+        true
+    }
+
+    /// Runs cycles until `until` holds after one of them, `false` if the program ended first
+    pub(crate) fn run_until(
+        &mut self,
+        mut observer: impl FnMut(&Tick),
+        until: impl Fn(&Vm) -> bool,
+    ) -> bool {
+        while self.step(&mut observer) {
+            if until(self) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Runs the whole program
+    pub(crate) fn run(&mut self, observer: impl FnMut(&Tick)) {
+        self.run_until(observer, |_| false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_10::vm::{Instruction, Latency, Vm};
+
+    /// The handheld runs with the default latency and is only ever observed through ticks
+    impl Vm<'_> {
+        fn with_latency(mut self, latency: Latency) -> Self {
+            self.latency = latency;
+            self
+        }
+
+        /// Cycles completed so far
+        fn cycle(&self) -> u64 {
+            self.cycle
+        }
+
+        fn register(&self, name: char) -> i64 {
+            self.registers.get(name)
+        }
+
+        fn is_halted(&self) -> bool {
+            self.pc >= self.program.len()
+        }
+    }
+
+    #[test]
+    fn parses_or_fails() {
+        assert_eq!(Ok(Instruction::Noop), "noop".parse());
+        assert_eq!(
+            Ok(Instruction::Add {
+                register: 'y',
+                value: -3
+            }),
+            "addy -3".parse()
+        );
+        assert!("addx".parse::<Instruction>().is_err());
+        assert!("addx three".parse::<Instruction>().is_err());
+        assert!("addXY 3".parse::<Instruction>().is_err());
+        assert!("mul 3".parse::<Instruction>().is_err());
+    }
+
+    #[test]
+    fn observes_every_cycle() {
+        let program = ["noop", "addx 3", "addx -5"].map(|line| line.parse().unwrap());
+        let mut trace = vec![];
+        Vm::handheld(&program).run(|tick| trace.push((tick.cycle, tick.registers.get('x'))));
+        assert_eq!(vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)], trace);
+    }
+
+    #[test]
+    fn steps_until() {
+        let program = ["addx 3", "addy 2", "noop", "addx 1"].map(|line| line.parse().unwrap());
+        let latency = Latency { noop: 1, add: 3 };
+        let mut vm = Vm::new(&program).with_latency(latency);
+        assert!(vm.run_until(|_| {}, |vm| vm.register('y') == 2));
+        assert_eq!((6, 3), (vm.cycle(), vm.register('x')));
+        assert!(vm.step(&mut |tick| assert_eq!(Instruction::Noop, *tick.instruction)));
+        assert!(!vm.run_until(|_| {}, |vm| vm.register('x') > 10));
+        assert!(vm.is_halted());
+        assert_eq!((10, 4), (vm.cycle(), vm.register('x')));
+        assert!(!vm.step(&mut |_| panic!("nothing left to run")));
+    }
+}
@@ -1,3 +1,6 @@
+use crate::day_11::parse::parse_all_monkeys_def;
+use crate::day_11::part_2::worry_level_reducer;
+use crate::solver::{line_of, ParseError, SolveError, Solver};
 use std::cmp;
 use std::collections::VecDeque;
 
@@ -125,10 +128,59 @@ pub(crate) fn exec_rounds(
     items.iter().take(2).product()
 }
 
+pub(crate) struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Monkey>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let (rest, monkeys) = parse_all_monkeys_def(input)
+            .map_err(|e| ParseError::new(format!("Invalid monkey definitions: {e}")))?;
+        if !rest.trim().is_empty() {
+            return Err(ParseError::at_line(
+                line_of(input, rest),
+                "Invalid monkey definition",
+            ));
+        }
+        for monkey in &monkeys {
+            if monkey.test_divisible_by == 0 {
+                return Err(ParseError::new(format!(
+                    "Monkey {} can't test divisibility by 0",
+                    monkey.identifier
+                )));
+            }
+            for to in [monkey.monkey_if_true, monkey.monkey_if_false] {
+                if to as usize >= monkeys.len() {
+                    return Err(ParseError::new(format!(
+                        "Monkey {} throws to unknown monkey {to}",
+                        monkey.identifier
+                    )));
+                }
+            }
+        }
+        Ok(monkeys)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        let mut monkeys = input.clone();
+        Ok(exec_rounds(20, &mut monkeys, &|i| i / 3))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        let mut monkeys = input.clone();
+        let reducer = worry_level_reducer(&monkeys);
+        Ok(exec_rounds(10_000, &mut monkeys, &reducer))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_11::parse::parse_all_monkeys_def;
-    use crate::day_11::Monkey;
+    use crate::day_11::{Day11, Monkey};
+    use crate::solver::Solver;
     use crate::utils::io::input_string;
 
     pub(crate) const SAMPLE_DEF: &str = "\
@@ -165,11 +217,23 @@ Monkey 3:
     }
 
     fn puzzle_input() -> String {
-        input_string("day_11.txt")
+        input_string("day_11.txt").unwrap()
     }
 
     pub(crate) fn puzzle_monkeys() -> Vec<Monkey> {
         let str = puzzle_input();
         parse_all_monkeys_def(str.as_str()).unwrap().1
     }
+
+    #[test]
+    fn rejects_division() {
+        let def = SAMPLE_DEF.replace("old * 19", "old / 0");
+        assert!(Day11.parse(&def).is_err());
+    }
+
+    #[test]
+    fn reports_the_line_of_an_invalid_monkey() {
+        let def = SAMPLE_DEF.replace("Monkey 1:", "Monkey one:");
+        assert_eq!(Some(8), Day11.parse(&def).unwrap_err().line);
+    }
 }
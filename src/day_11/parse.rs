@@ -13,6 +13,7 @@ fn parse_term(def: &str) -> IResult<&str, Term> {
     alt((parse_old, parse_constant))(def)
 }
 
+/// Monkeys only add or multiply: reducing worry levels in part 2 relies on it
 fn parse_op(def: &str) -> IResult<&str, Op> {
     let parse_mul = value(Op::Mul, tag(" * "));
     let parse_add = value(Op::Add, tag(" + "));
@@ -92,6 +93,13 @@ mod tests {
         )
     }
 
+    #[test]
+    fn only_adds_or_multiplies() {
+        for sample in ["Operation: new = old / 0\n", "Operation: new = 3 - old\n"] {
+            assert!(parse_operation(sample).is_err());
+        }
+    }
+
     #[test]
     fn can_parse_monkey() {
         let monkey_def = "Monkey 0:
@@ -1,7 +1,7 @@
 use crate::day_11::Monkey;
 
 // The tip here is that we don't need to work with the level, but only the value divided by the common divisor to every monkey operation
-fn worry_level_reducer(monkeys: &Vec<Monkey>) -> impl Fn(u64) -> u64 {
+pub(crate) fn worry_level_reducer(monkeys: &Vec<Monkey>) -> impl Fn(u64) -> u64 {
     let pgcm: u64 = monkeys.iter().map(|m| m.test_divisible_by).product();
     move |i: u64| -> u64 { i % pgcm }
 }
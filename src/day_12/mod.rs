@@ -1,118 +1,57 @@
 mod part_1;
-
-use linked_hash_set::LinkedHashSet;
-use std::cmp;
-use std::cmp::{max, min, Ordering};
-use std::collections::{HashMap, HashSet, VecDeque};
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub(crate) struct Coord {
-    x: usize,
-    y: usize,
-}
-
-impl Coord {
-    pub(crate) fn distance_to(&self, other: &Coord) -> u64 {
-        let max_x = max(self.x, other.x);
-        let min_x = min(self.x, other.x);
-        let max_y = max(self.y, other.y);
-        let min_y = min(self.y, other.y);
-        let d_x = (max_x - min_x) as f64;
-        let d_y = (max_y - min_y) as f64;
-        (d_x * d_x / d_y * d_y).sqrt().round() as u64
-    }
+mod part_2;
+
+use crate::solver::{ParseError, SolveError, Solver};
+use crate::utils::geom::Coord;
+use crate::utils::grid::Grid;
+use std::collections::VecDeque;
+
+/// Elevations from `a` to `z`, with `S` (at `a`) and `E` (at `z`) marking where to go from and to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Heightmap {
+    grid: Grid<char>,
+    start: Coord,
+    end: Coord,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-pub(crate) struct Grid {
-    inner: Vec<Vec<char>>,
-}
-
-impl Grid {
-    fn new(inner: Vec<Vec<char>>) -> Self {
-        Grid { inner }
-    }
-
-    fn width(&self) -> usize {
-        self.inner.first().unwrap().len()
-    }
-
-    fn height(&self) -> usize {
-        self.inner.len()
+impl Heightmap {
+    fn new(grid: Grid<char>) -> Result<Self, ParseError> {
+        let find = |marker: char| {
+            let count = grid.iter().filter(|(_, c)| **c == marker).count();
+            match grid.position(|c| *c == marker) {
+                Some(coord) if count == 1 => Ok(coord),
+                _ => Err(ParseError::new(format!(
+                    "Expecting exactly one {marker}, found {count}"
+                ))),
+            }
+        };
+        let start = find('S')?;
+        let end = find('E')?;
+        Ok(Heightmap { grid, start, end })
     }
 
     fn at(&self, coord: &Coord) -> char {
-        *self.inner.get(coord.x).unwrap().get(coord.y).unwrap()
-    }
-
-    fn find(&self, target: char) -> Coord {
-        self.inner
-            .iter()
-            .enumerate()
-            .find_map(|(i, vec)| {
-                vec.iter().enumerate().find_map(|(j, c)| {
-                    if *c == target {
-                        Some(Coord { x: i, y: j })
-                    } else {
-                        None
-                    }
-                })
-            })
-            .unwrap()
+        self.grid[coord]
     }
 
-    fn end(&self) -> Coord {
-        self.find('E')
+    pub(crate) fn start(&self) -> Coord {
+        self.start
     }
 
-    fn start(&self) -> Coord {
-        self.find('S')
+    pub(crate) fn end(&self) -> Coord {
+        self.end
     }
 }
 
-impl Coord {
-    fn left(&self) -> Option<Coord> {
-        self.y
-            .checked_add_signed(-1)
-            .map(|y| Coord { x: self.x, y })
-    }
-    fn up(&self) -> Option<Coord> {
-        self.x
-            .checked_add_signed(-1)
-            .map(|x| Coord { x, y: self.y })
-    }
-    fn right(&self, size: usize) -> Option<Coord> {
-        if self.y + 1 >= size {
-            return None;
-        }
-        Some(Coord {
-            x: self.x,
-            y: self.y + 1,
-        })
-    }
-    fn bottom(&self, size: usize) -> Option<Coord> {
-        if self.x + 1 >= size {
-            return None;
+pub(crate) fn parse_heightmap(input: &str) -> Result<Heightmap, ParseError> {
+    let grid = Grid::parse(input, |c| {
+        if c.is_ascii_lowercase() || c == 'S' || c == 'E' {
+            Ok(c)
+        } else {
+            Err(format!("Unexpected elevation {c:?}"))
         }
-        Some(Coord {
-            x: self.x + 1,
-            y: self.y,
-        })
-    }
-}
-
-fn valid_directions(grid: &Grid, origin: &Coord) -> Vec<Coord> {
-    let height = grid.height();
-    let width = grid.width();
-    vec![
-        origin.left(),
-        origin.up(),
-        origin.right(width),
-        origin.bottom(height),
-    ]
-    .into_iter()
-    .flatten()
-    .collect()
+    })?;
+    Heightmap::new(grid)
 }
 
 fn valid_elevation(origin: char, new: char) -> bool {
@@ -128,23 +67,10 @@ fn valid_elevation(origin: char, new: char) -> bool {
     new as u8 <= origin as u8 + 1
 }
 
-fn valid_elevation_reverse(origin: char, new: char) -> bool {
-    if origin == 'E' {
-        return new == 'z';
-    }
-    if origin == 'S' {
-        return true;
-    }
-    if origin == 'a' {
-        return new == 'S';
-    }
-    new as u8 >= origin as u8 - 1
-}
-
-fn allowed_directions(grid: &Grid, origin: &Coord) -> Vec<Coord> {
+fn allowed_directions(grid: &Heightmap, origin: &Coord) -> Vec<Coord> {
     let origin_elevation = grid.at(origin);
-    valid_directions(grid, origin)
-        .into_iter()
+    grid.grid
+        .neighbours(origin)
         .filter(|coord| {
             let elevation = grid.at(coord);
             valid_elevation(origin_elevation, elevation)
@@ -152,196 +78,134 @@ fn allowed_directions(grid: &Grid, origin: &Coord) -> Vec<Coord> {
         .collect()
 }
 
-fn allowed_directions_reverse(grid: &Grid, origin: &Coord) -> Vec<Coord> {
+/// Cells from which `origin` can be reached in one step
+fn allowed_directions_reverse(grid: &Heightmap, origin: &Coord) -> Vec<Coord> {
     let origin_elevation = grid.at(origin);
-    valid_directions(grid, origin)
-        .into_iter()
+    grid.grid
+        .neighbours(origin)
         .filter(|coord| {
             let elevation = grid.at(coord);
-            valid_elevation_reverse(origin_elevation, elevation)
+            valid_elevation(elevation, origin_elevation)
         })
         .collect()
 }
 
-fn walk_to_iter(grid: &Grid, origin: &Coord, dest: &Coord) -> Vec<Vec<Coord>> {
-    let mut visited: HashSet<Coord> = HashSet::new();
-    let mut to_visit: VecDeque<(Coord, Vec<Coord>)> = VecDeque::new();
-    let mut solutions: Vec<Vec<Coord>> = vec![];
-    to_visit.push_back((origin.clone(), vec![]));
-    while let Some((coord, mut traversed_so_far)) = to_visit.pop_front() {
-        if !visited.contains(&coord) {
-            if *dest == coord {
-                solutions.push(traversed_so_far);
-                solutions.sort_by_key(|s| s.len());
-            } else {
-                visited.insert(coord.clone());
-                traversed_so_far.push(coord.clone());
-                // if let Some(solution) = solutions.first() {
-                //     if solution.len() <= traversed_so_far.len() {
-                //         // Stop here, there's no need to go further
-                //         continue;
-                //     }
-                // }
-                if traversed_so_far.len() <= 4 {
-                    let collected = traversed_so_far
-                        .iter()
-                        .map(|c| grid.at(c))
-                        .collect::<String>();
-                    println!("{collected:?}")
-                }
-                for direction in allowed_directions(grid, &coord) {
-                    to_visit.push_back((direction, traversed_so_far.clone()));
-                }
-            }
-        }
-    }
-    solutions
+/// Shortest walk found by `shortest_path`, `steps` going from the origin to the destination (both included)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Path {
+    pub(crate) distance: usize,
+    pub(crate) steps: Vec<Coord>,
 }
 
-fn walk_to_recursive(
-    grid: &Grid,
-    origin: &Coord,
-    destination: &Coord,
-    visited: &mut HashMap<Coord, usize>,
-    traversed: &mut LinkedHashSet<Coord>,
-    solutions: &mut Vec<LinkedHashSet<Coord>>,
-) {
-    let mut directions_possible = allowed_directions(grid, origin);
-    directions_possible.sort_by(|coord_1, coord_2| {
-        let c1 = grid.at(coord_1);
-        let c2 = grid.at(coord_2);
-        if c1 > c2 {
-            Ordering::Less
-        } else if c1 < c2 {
-            Ordering::Greater
-        } else {
-            // pick the shortest path geometrically
-            let delta_1 = coord_1.distance_to(destination);
-            let delta_2 = coord_2.distance_to(destination);
-            delta_1.cmp(&delta_2)
-        }
-    });
-    for coord in directions_possible {
-        let mut traversed_so_far = traversed.clone();
-        if traversed_so_far.contains(&coord) {
-            continue; // next direction
-        }
-        // if visited.contains(&coord) {
-        //     continue; // abort here, we have visited it already
-        // }
-        // println!("visited: {visited:?}");
-        if let Some(v) = visited.get(&coord) {
-            println!("Already visited this coords after {v} steps");
-            if *v < traversed.len() {
-                println!("  No need to revisit, there's a shorter path to it");
-                continue; // we already visited it with a shorter path
+/// Every step costs the same, so a BFS is all it takes: the first time a cell is reached is the shortest.
+/// Only the predecessor of each cell is kept, the path being rebuilt backwards once `dest` is reached
+pub(crate) fn shortest_path(grid: &Heightmap, origin: &Coord, dest: &Coord) -> Option<Path> {
+    let (width, height) = (grid.grid.width(), grid.grid.height());
+    let mut previous: Grid<Option<Coord>> = Grid::filled(width, height, None);
+    let mut visited = Grid::filled(width, height, false);
+    let mut to_visit = VecDeque::from([*origin]);
+    visited[origin] = true;
+    while let Some(coord) = to_visit.pop_front() {
+        if coord == *dest {
+            let mut steps = vec![coord];
+            while let Some(prev) = &previous[steps.last().unwrap()] {
+                steps.push(*prev);
             }
+            steps.reverse();
+            return Some(Path {
+                distance: steps.len() - 1,
+                steps,
+            });
         }
-        if &coord == destination {
-            solutions.push(traversed_so_far.clone());
-            solutions.sort_by_key(|f| f.len());
-            return;
-        }
-        if visited.len() > 1000 {
-            println!("Stopping, too many nodes visited");
-            return;
-        }
-        // let collected = traversed_so_far
-        //     .iter()
-        //     .map(|c| grid.at(c))
-        //     .collect::<String>();
-        // println!("collected: {collected:?}");
-        traversed_so_far.insert(coord.clone());
-        // visited.insert(coord.clone());
-        let v = visited.entry(coord.clone()).or_insert(usize::MAX);
-        *v = min(*v, traversed.len());
-        if let Some(found) = solutions.first() {
-            if traversed_so_far.len() > found.len() {
-                break;
+        for next in allowed_directions(grid, &coord) {
+            if !visited[&next] {
+                visited[&next] = true;
+                previous[&next] = Some(coord);
+                to_visit.push_back(next);
             }
         }
-        walk_to_recursive(
-            grid,
-            &coord,
-            destination,
-            visited,
-            &mut traversed_so_far,
-            solutions,
-        );
     }
+    None
 }
 
-fn walk_from(
-    grid: &Grid,
-    origin: &Coord,
-    traversed: &mut LinkedHashSet<Coord>,
-    solutions: &mut Vec<LinkedHashSet<Coord>>,
-) {
-    let mut directions_possible = allowed_directions_reverse(grid, origin);
-    directions_possible.sort_by_key(|f| grid.at(f));
-    for coord in directions_possible {
-        let mut traversed_so_far = traversed.clone();
-        if traversed_so_far.contains(&coord) {
-            continue; // next direction
-        }
-        if grid.at(&coord) == 'S' {
-            solutions.push(traversed_so_far.clone());
-            solutions.sort_by_key(|f| f.len());
-            return;
-        }
-        traversed_so_far.insert(coord.clone());
-        if let Some(found) = solutions.first() {
-            if traversed_so_far.len() > found.len() {
-                break;
+/// Number of steps from every cell to the destination of `distances_to`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DistanceField {
+    distances: Grid<Option<usize>>,
+}
+
+impl DistanceField {
+    /// `None` when the destination can't be reached from `coord`, or `coord` is off the map
+    pub(crate) fn get(&self, coord: &Coord) -> Option<usize> {
+        self.distances.get(coord).copied().flatten()
+    }
+}
+
+/// A single BFS walking backwards from `dest`, answering for any start at once
+pub(crate) fn distances_to(grid: &Heightmap, dest: &Coord) -> DistanceField {
+    let mut distances = Grid::filled(grid.grid.width(), grid.grid.height(), None);
+    let mut to_visit = VecDeque::from([(*dest, 0)]);
+    distances[dest] = Some(0);
+    while let Some((coord, distance)) = to_visit.pop_front() {
+        for prev in allowed_directions_reverse(grid, &coord) {
+            if distances[&prev].is_none() {
+                distances[&prev] = Some(distance + 1);
+                to_visit.push_back((prev, distance + 1));
             }
         }
-        walk_from(grid, &coord, &mut traversed_so_far, solutions);
     }
+    DistanceField { distances }
 }
 
-pub(crate) fn paths(grid: &Grid) -> Vec<LinkedHashSet<Coord>> {
-    let mut solutions = vec![];
-    let mut traversed = LinkedHashSet::new();
-    walk_to_recursive(
-        grid,
-        &grid.start(),
-        &grid.end(),
-        &mut HashMap::new(),
-        // &mut HashSet::new(),
-        &mut traversed,
-        &mut solutions,
-    );
-    solutions
+/// Fewest steps to `E` from any cell at the lowest elevation (`S` included)
+pub(crate) fn shortest_from_lowest(grid: &Heightmap) -> Option<usize> {
+    let field = distances_to(grid, &grid.end());
+    grid.grid
+        .iter()
+        .filter(|(_, elevation)| matches!(elevation, 'a' | 'S'))
+        .filter_map(|(coord, _)| field.get(&coord))
+        .min()
 }
 
-pub(crate) fn paths_rev(grid: &Grid) -> Vec<LinkedHashSet<Coord>> {
-    let mut solutions = vec![];
-    let mut traversed = LinkedHashSet::new();
-    walk_from(grid, &grid.end(), &mut traversed, &mut solutions);
-    solutions
+pub(crate) struct Day12;
+
+impl Solver for Day12 {
+    const DAY: u8 = 12;
+    type Input = Heightmap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_heightmap(input)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        shortest_path(input, &input.start(), &input.end())
+            .map(|path| path.distance)
+            .ok_or(SolveError::NoSolution("E can't be reached".to_string()))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        shortest_from_lowest(input).ok_or(SolveError::NoSolution(
+            "E can't be reached from any a".to_string(),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day_12::{
-        allowed_directions, allowed_directions_reverse, paths, paths_rev, valid_elevation,
-        walk_to_iter, walk_to_recursive, Coord, Grid,
+        allowed_directions, allowed_directions_reverse, distances_to, parse_heightmap,
+        shortest_from_lowest, shortest_path, valid_elevation, Coord, Heightmap,
     };
-    use crate::utils::io::input_file_lines;
-
-    pub(crate) fn puzzle_input() -> Grid {
-        Grid::new(
-            input_file_lines("day_12.txt")
-                .unwrap()
-                .map(|line| line.unwrap().chars().collect::<Vec<_>>())
-                .collect(),
-        )
+    use crate::utils::io::input_string;
+
+    pub(crate) fn puzzle_input() -> Heightmap {
+        parse_heightmap(&input_string("day_12.txt").unwrap()).unwrap()
     }
 
-    fn sample() -> Grid {
-        let raw = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi";
-        let vec = raw.lines().map(|c| c.chars().collect()).collect();
-        Grid::new(vec)
+    fn sample() -> Heightmap {
+        parse_heightmap("Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi").unwrap()
     }
 
     #[test]
@@ -358,32 +222,30 @@ mod tests {
     #[test]
     fn check_few_valid_directions() {
         let grid = sample();
-        let directions = allowed_directions(&grid, &Coord { x: 0, y: 2 });
+        let directions = allowed_directions(&grid, &Coord { x: 2, y: 0 });
         assert_eq!(
-            vec![Coord { x: 0, y: 1 }, Coord { x: 1, y: 2 },],
+            vec![Coord { x: 1, y: 0 }, Coord { x: 2, y: 1 },],
             directions
         );
         let directions = allowed_directions(&grid, &Coord { x: 2, y: 2 });
         assert_eq!(
             vec![
-                Coord { x: 2, y: 1 },
                 Coord { x: 1, y: 2 },
-                Coord { x: 3, y: 2 }
+                Coord { x: 2, y: 1 },
+                Coord { x: 2, y: 3 }
             ],
             directions
         );
-        let directions = allowed_directions(&grid, &Coord { x: 1, y: 2 });
-        println!("{directions:?}")
     }
 
     #[test]
     fn check_few_valid_directions_reverse() {
         let grid = sample();
-        let directions = allowed_directions_reverse(&grid, &Coord { x: 2, y: 3 });
+        let directions = allowed_directions_reverse(&grid, &Coord { x: 3, y: 2 });
         assert_eq!(
             vec![
-                Coord { x: 1, y: 3 },
-                Coord { x: 2, y: 4 },
+                Coord { x: 3, y: 1 },
+                Coord { x: 4, y: 2 },
                 Coord { x: 3, y: 3 }
             ],
             directions
@@ -394,47 +256,58 @@ mod tests {
     fn check_start_end() {
         let grid = sample();
         assert_eq!(Coord { x: 0, y: 0 }, grid.start());
-        assert_eq!(Coord { x: 2, y: 5 }, grid.end());
+        assert_eq!(Coord { x: 5, y: 2 }, grid.end());
     }
 
     #[test]
-    fn check_walk_to_recursive() {
-        let grid = sample();
-        let solutions = paths(&grid);
-        let s = solutions.first().unwrap();
-        let collected = s.iter().map(|coord| grid.at(coord)).collect::<String>();
-        assert_eq!("abcccdefghijklmnopqrstuvwxxxyz".to_string(), collected);
-        let len = s.len() + 1;
-        assert_eq!(31, len);
+    fn check_markers() {
+        assert!(parse_heightmap("SabE\nabcS").is_err());
+        assert!(parse_heightmap("Sabc\nabcd").is_err());
+        assert!(parse_heightmap("SabE\nab1d").is_err());
+    }
+
+    /// Each step is to an adjacent cell, at most one higher
+    pub(crate) fn assert_walkable(grid: &Heightmap, steps: &[Coord]) {
+        for pair in steps.windows(2) {
+            assert!(
+                allowed_directions(grid, &pair[0]).contains(&pair[1]),
+                "can't go from {:?} to {:?}",
+                pair[0],
+                pair[1]
+            );
+        }
     }
 
     #[test]
-    fn check_walk_to_iter() {
+    fn check_shortest_path() {
         let grid = sample();
-        let solutions = walk_to_iter(&grid, &grid.start(), &grid.end());
-        let s = solutions.first().unwrap();
-        let collected = s.iter().map(|coord| grid.at(coord)).collect::<String>();
-        assert_eq!("Sabcccdefghijklmnopqrstuvwxxxyz".to_string(), collected);
-        let len = s.len();
-        assert_eq!(31, len);
+        let path = shortest_path(&grid, &grid.start(), &grid.end()).unwrap();
+        assert_eq!(31, path.distance);
+        assert_eq!(32, path.steps.len());
+        assert_eq!(Some(&grid.start()), path.steps.first());
+        assert_eq!(Some(&grid.end()), path.steps.last());
+        assert_walkable(&grid, &path.steps);
+    }
+
+    #[test]
+    fn check_unreachable() {
+        let grid = parse_heightmap("Sbcz\nabzE").unwrap();
+        assert_eq!(None, shortest_path(&grid, &grid.start(), &grid.end()));
+        let path = shortest_path(&grid, &grid.start(), &grid.start()).unwrap();
+        assert_eq!(0, path.distance);
     }
 
     #[test]
-    fn check_walk_from() {
+    fn check_distance_field() {
         let grid = sample();
-        let solutions = paths_rev(&grid);
-        let nb_solutions = solutions.len();
-        println!("{nb_solutions:?}");
-        let s = solutions.first().unwrap();
-        println!("{}", s.len());
-        let collected = s
-            .iter()
-            .map(|coord| grid.at(coord))
-            .rev()
-            .collect::<String>();
-        println!("{collected:?}");
-        assert_eq!("abcccdefghijklmnopqrstuvwxxxyz".to_string(), collected);
-        let len = s.len() + 1;
-        assert_eq!(31, len);
+        let field = distances_to(&grid, &grid.end());
+        assert_eq!(Some(0), field.get(&grid.end()));
+        assert_eq!(Some(31), field.get(&grid.start()));
+        assert_eq!(
+            shortest_path(&grid, &Coord { x: 0, y: 4 }, &grid.end()).map(|p| p.distance),
+            field.get(&Coord { x: 0, y: 4 })
+        );
+        assert_eq!(None, field.get(&Coord { x: 8, y: 0 }));
+        assert_eq!(Some(29), shortest_from_lowest(&grid));
     }
 }
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::day_12::tests::puzzle_input;
+    use crate::day_12::{distances_to, shortest_from_lowest, shortest_path};
+
+    #[test]
+    fn solution() {
+        let grid = puzzle_input();
+        let best = shortest_from_lowest(&grid).unwrap();
+        assert!(best <= 481);
+        // the distance field agrees with a forward search from the best start found
+        let field = distances_to(&grid, &grid.end());
+        let (start, distance) = grid
+            .grid
+            .coords()
+            .filter(|coord| grid.at(coord) == 'a')
+            .filter_map(|coord| field.get(&coord).map(|distance| (coord, distance)))
+            .min_by_key(|(_, distance)| *distance)
+            .unwrap();
+        assert_eq!(best, distance);
+        let path = shortest_path(&grid, &start, &grid.end()).unwrap();
+        assert_eq!(best, path.distance);
+        assert_eq!(480, best);
+    }
+}
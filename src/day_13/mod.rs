@@ -1,163 +1,170 @@
 mod part_1;
+mod part_2;
+// reference implementation, `Packet` is checked against
+#[cfg(test)]
 mod soluce_serde;
 
+use crate::day_13::part_1::sum_right_orders;
+use crate::day_13::part_2::{decoder_key, divider_packets};
+use crate::solver::{ParseError, SolveError, Solver};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::u64 as u64p;
+use nom::combinator::{all_consuming, cut, map, success};
+use nom::multi::many0;
+use nom::sequence::{delimited, pair, preceded};
+use nom::IResult;
 use std::cmp::Ordering;
-use std::collections::VecDeque;
-use std::str::Chars;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub(crate) enum Item {
     Int(u64),
     List(Vec<Item>),
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
-pub(crate) struct Packet {
-    inner: VecDeque<Item>,
+/// An int meeting a list is compared as a list holding only that int
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Item::Int(left), Item::Int(right)) => left.cmp(right),
+            (Item::List(left), Item::List(right)) => left.as_slice().cmp(right.as_slice()),
+            (Item::Int(_), Item::List(right)) => std::slice::from_ref(self).cmp(right.as_slice()),
+            (Item::List(left), Item::Int(_)) => left.as_slice().cmp(std::slice::from_ref(other)),
+        }
+    }
 }
 
-impl Packet {
-    fn pop(&mut self) -> Option<Item> {
-        self.inner.pop_front()
+impl PartialOrd for Item {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
+}
 
-    fn back(&self) -> Option<&Item> {
-        self.inner.back()
+/// As the ordering has it: `1` equals `[1]`
+impl PartialEq for Item {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
+}
 
-    fn push(&mut self, item: Item) {
-        self.inner.push_back(item)
-    }
+impl Eq for Item {}
+
+/// Packets in the right order compare as `lhs < rhs`
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Packet {
+    items: Vec<Item>,
 }
 
 impl From<Vec<Item>> for Packet {
-    fn from(value: Vec<Item>) -> Self {
-        Packet {
-            inner: VecDeque::from(value),
-        }
+    fn from(items: Vec<Item>) -> Self {
+        Packet { items }
     }
 }
 
-pub(crate) fn is_ordered(lhs: &mut Packet, rhs: &mut Packet) -> bool {
-    match (lhs.pop(), rhs.pop()) {
-        (None, Some(_)) => true,
-        (Some(_), None) => false,
-        (None, None) => true,
-        (Some(Item::Int(left)), Some(Item::Int(right))) => match left.cmp(&right) {
-            Ordering::Less => true,
-            Ordering::Greater => false,
-            Ordering::Equal => is_ordered(lhs, rhs),
-        },
-        (Some(Item::List(l)), Some(Item::Int(i))) => {
-            println!("list compare int");
-            lhs.push(Item::List(l));
-            let mut rhs = rhs.clone();
-            rhs.push(Item::List(vec![Item::Int(i)]));
-            is_ordered(lhs, &mut rhs)
-        }
-        (Some(Item::Int(i)), Some(Item::List(l))) => {
-            let mut lhs = lhs.clone();
-            lhs.push(Item::List(vec![Item::Int(i)]));
-            rhs.push(Item::List(l));
-            is_ordered(&mut lhs, rhs)
-        }
-        (Some(Item::List(list_left)), Some(Item::List(list_right))) => {
-            println!("left: {list_left:?}");
-            println!("right: {list_right:?}");
-            let mut new_lhs = list_left.into();
-            let mut new_rhs = list_right.into();
-            is_ordered(&mut new_lhs, &mut new_rhs) && is_ordered(lhs, rhs)
-        }
-    }
+fn parse_item(input: &str) -> IResult<&str, Item> {
+    alt((map(u64p, Item::Int), map(parse_list, Item::List)))(input)
 }
 
-fn parse_list(chars: &mut Chars) -> Vec<Item> {
-    let d = chars.clone().collect::<String>();
-    let mut items = Vec::new();
-    while let Some(c) = chars.next() {
-        if c == ']' {
-            break;
-        }
-        if let Some((closed, item)) = parse_item(c, chars) {
-            items.push(item);
-            if closed {
-                break;
-            }
+fn parse_list(input: &str) -> IResult<&str, Vec<Item>> {
+    // past a comma an item must follow, and past the items the list must be closed:
+    // failing right there tells where the packet is invalid
+    let items = map(
+        pair(parse_item, many0(preceded(tag(","), cut(parse_item)))),
+        |(first, others)| std::iter::once(first).chain(others).collect(),
+    );
+    delimited(tag("["), alt((items, success(vec![]))), cut(tag("]")))(input)
+}
+
+impl FromStr for Packet {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        match all_consuming(parse_list)(line) {
+            Ok((_, items)) => Ok(items.into()),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(format!(
+                "Invalid packet {line:?} at column {}",
+                line.len() - e.input.len() + 1
+            )),
+            Err(nom::Err::Incomplete(_)) => Err(format!("Incomplete packet {line:?}")),
         }
     }
-    items
 }
 
-fn parse_item(c: char, chars: &mut Chars) -> Option<(bool, Item)> {
-    match c {
-        ',' => None,
-        '[' => {
-            let list = parse_list(chars);
-            Some((false, Item::List(list)))
-        }
-        value => {
-            let mut str = format!("{value}");
-            let mut last = ',';
-            let rest = chars
-                .take_while(|c| {
-                    last = *c;
-                    c.is_ascii_digit()
-                })
-                .collect::<String>();
-            str += rest.as_str();
-            match str.parse() {
-                Err(_) => {
-                    println!("Invalid {str}");
-                    None
-                }
-                Ok(val) => Some((last == ']', Item::Int(val))),
-            }
+fn write_list(f: &mut Formatter<'_>, items: &[Item]) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
         }
+        write!(f, "{item}")?;
     }
+    write!(f, "]")
 }
 
-pub(crate) fn parse(str: String) -> Packet {
-    let mut chars = str.chars();
-    chars.next(); // [
-    let list = parse_list(&mut chars);
-    list.into()
+impl Display for Item {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Item::Int(n) => write!(f, "{n}"),
+            Item::List(items) => write_list(f, items),
+        }
+    }
 }
 
-impl ToString for Packet {
-    fn to_string(&self) -> String {
-        "[".to_string()
-            + self
-                .inner
-                .iter()
-                .map(|i| i.to_string())
-                .collect::<Vec<String>>()
-                .join(",")
-                .as_str()
-            + "]"
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_list(f, &self.items)
     }
 }
 
-impl ToString for Item {
-    fn to_string(&self) -> String {
-        match self {
-            Item::Int(u) => u.to_string(),
-            Item::List(l) => {
-                "[".to_string()
-                    + l.iter()
-                        .map(|i| i.to_string())
-                        .collect::<Vec<String>>()
-                        .join(",")
-                        .as_str()
-                    + "]"
+pub(crate) struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<(Packet, Packet)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let mut packets = vec![];
+        for (i, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
             }
+            packets.push(line.parse().map_err(|e| ParseError::at_line(i + 1, e))?);
         }
+        if !packets.len().is_multiple_of(2) {
+            return Err(ParseError::new("Packets must come in pairs"));
+        }
+        let mut packets = packets.into_iter();
+        let mut pairs = vec![];
+        while let (Some(lhs), Some(rhs)) = (packets.next(), packets.next()) {
+            pairs.push((lhs, rhs));
+        }
+        Ok(pairs)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(sum_right_orders(input))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        let packets = input
+            .iter()
+            .flat_map(|(lhs, rhs)| [lhs.clone(), rhs.clone()])
+            .collect::<Vec<_>>();
+        Ok(decoder_key(&packets, &divider_packets()) as u64)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_13::{is_ordered, parse, Item, Packet};
+    use crate::day_13::soluce_serde::Node;
+    use crate::day_13::{Item, Packet};
     use crate::utils::io::input_file_lines;
+    use quickcheck::{Arbitrary, Gen};
 
     pub(crate) fn sample_pairs() -> Vec<(Packet, Packet)> {
         vec![
@@ -253,9 +260,9 @@ mod tests {
     }
 
     fn sample_pair_n_order(n: usize) -> bool {
-        let mut samples = sample_pairs();
-        let (lhs, rhs) = samples.get_mut(n).unwrap();
-        is_ordered(lhs, rhs)
+        let samples = sample_pairs();
+        let (lhs, rhs) = &samples[n];
+        lhs < rhs
     }
 
     #[test]
@@ -299,8 +306,8 @@ mod tests {
 
     fn check_parsing_n(idx: usize, input: (&str, &str)) {
         let (l, r) = input;
-        let l = parse(l.to_string());
-        let r = parse(r.to_string());
+        let l = l.parse::<Packet>().unwrap();
+        let r = r.parse::<Packet>().unwrap();
         let samples = sample_pairs();
         let (el, er) = samples.get(idx).unwrap();
         assert_eq!((el, er), (&l, &r));
@@ -352,10 +359,10 @@ mod tests {
 
     #[test]
     fn check_nested() {
-        let a = parse("[[0]]".to_string());
+        let a = "[[0]]".parse::<Packet>().unwrap();
         assert_eq!(Packet::from(vec![Item::List(vec![Item::Int(0)])]), a);
 
-        let a = parse("[[1],4]".to_string());
+        let a = "[[1],4]".parse::<Packet>().unwrap();
         assert_eq!(
             Packet::from(vec![Item::List(vec![Item::Int(1)]), Item::Int(4)]),
             a
@@ -372,8 +379,62 @@ mod tests {
             .map(|chunk| {
                 let fst = &chunk[0];
                 let snd = &chunk[1];
-                (parse(fst.clone()), parse(snd.clone()))
+                (fst.parse().unwrap(), snd.parse().unwrap())
             })
             .collect()
     }
+
+    #[test]
+    fn rejects_invalid_packets() {
+        assert_eq!(
+            Err("Invalid packet \"[1,[2,x]]\" at column 7".to_string()),
+            "[1,[2,x]]".parse::<Packet>()
+        );
+        assert!("[1,2".parse::<Packet>().is_err());
+        assert!("[1,2]]".parse::<Packet>().is_err());
+        assert!("[1,,2]".parse::<Packet>().is_err());
+        assert!("1".parse::<Packet>().is_err());
+        assert!("".parse::<Packet>().is_err());
+    }
+
+    #[test]
+    fn ints_compare_as_lists() {
+        assert_eq!(Item::Int(1), Item::List(vec![Item::Int(1)]));
+        assert!(Item::Int(1) < Item::List(vec![Item::Int(1), Item::Int(0)]));
+        assert!(Item::List(vec![]) < Item::Int(0));
+    }
+
+    // small ints and short lists, for ties to happen
+    fn arbitrary_item(g: &mut Gen, depth: usize) -> Item {
+        if depth == 0 || bool::arbitrary(g) {
+            Item::Int(u64::arbitrary(g) % 11)
+        } else {
+            let len = usize::arbitrary(g) % 5;
+            Item::List((0..len).map(|_| arbitrary_item(g, depth - 1)).collect())
+        }
+    }
+
+    impl Arbitrary for Packet {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let len = usize::arbitrary(g) % 5;
+            (0..len)
+                .map(|_| arbitrary_item(g, 4))
+                .collect::<Vec<_>>()
+                .into()
+        }
+    }
+
+    // PBT
+    #[quickcheck]
+    fn compares_as_serde_nodes(lhs: Packet, rhs: Packet) -> bool {
+        lhs.cmp(&rhs) == Node::from(&lhs).cmp(&Node::from(&rhs))
+    }
+
+    #[quickcheck]
+    fn parses_as_serde_json(packet: Packet) -> bool {
+        let line = packet.to_string();
+        let parsed = line.parse::<Packet>().unwrap();
+        parsed.to_string() == line
+            && Node::from(&parsed) == serde_json::from_str::<Node>(&line).unwrap()
+    }
 }
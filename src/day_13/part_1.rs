@@ -1,16 +1,18 @@
-use crate::day_13::{is_ordered, Item, Packet};
+use crate::day_13::Packet;
 
-fn sum_right_orders(pairs: &mut [(Packet, Packet)]) -> u64 {
+pub(crate) fn sum_right_orders(pairs: &[(Packet, Packet)]) -> u64 {
     pairs
-        .iter_mut()
+        .iter()
         .enumerate()
-        .filter_map(|(idx, (p1, p2))| {
-            if is_ordered(p1, p2) {
-                Some(idx as u64 + 1)
-            } else {
-                None
-            }
-        })
+        .filter_map(
+            |(idx, (p1, p2))| {
+                if p1 < p2 {
+                    Some(idx as u64 + 1)
+                } else {
+                    None
+                }
+            },
+        )
         .sum()
 }
 
@@ -19,19 +21,19 @@ mod tests {
     use crate::day_13::part_1::sum_right_orders;
     use crate::day_13::soluce_serde::Node;
     use crate::day_13::tests::{puzzle_input, sample_pairs};
-    use crate::day_13::{is_ordered, parse, Packet};
+    use crate::day_13::Packet;
     use crate::utils::io::input_file_lines;
 
     #[test]
     fn sample_sum() {
-        let mut sample = sample_pairs();
-        let sum = sum_right_orders(&mut sample);
+        let sample = sample_pairs();
+        let sum = sum_right_orders(&sample);
         assert_eq!(13, sum)
     }
 
     #[test]
     fn solution() {
-        let mut input = puzzle_input();
+        let input = puzzle_input();
         let lines = input_file_lines("day_13.txt")
             .unwrap()
             .map(Result::unwrap)
@@ -54,8 +56,8 @@ mod tests {
             i += 2;
             println!("\n");
         }
-        let sum = sum_right_orders(&mut input);
-        assert_eq!(123, sum);
+        let sum = sum_right_orders(&input);
+        assert_eq!(6428, sum);
     }
 
     #[test]
@@ -74,13 +76,13 @@ mod tests {
             println!("node_l: {node_l:?}");
             println!("node_r: {node_r:?}");
 
-            let mut packet_l = parse(l.clone());
-            let mut packet_r = parse(r.clone());
+            let packet_l = l.parse::<Packet>().unwrap();
+            let packet_r = r.parse::<Packet>().unwrap();
             println!("pack_l: {packet_l:?}");
             println!("pack_r: {packet_r:?}");
 
             let ordered_nodes = node_l < node_r;
-            let ordered_packets = is_ordered(&mut packet_l, &mut packet_r);
+            let ordered_packets = packet_l < packet_r;
 
             println!("ordered_nodes: {ordered_nodes:?}");
 
@@ -93,9 +95,9 @@ mod tests {
     #[test]
     fn test() {
         let input = "[[[],[[7,9,4,1]],[],[[2],7,[5,9,5,1],10],8],[[7],[[8]]],[2,10,[],[[4,7],6,6,4]],[2,[7,6],4,[9],[1]],[7,[[2,5,7,6]],[[10,10]],[1,3,9,[0,7,1]]]]";
-        let mut l = parse(input.to_string());
+        let l = input.parse::<Packet>().unwrap();
         let input = "[[0],[]]";
-        let mut r = parse(input.to_string());
-        assert!(is_ordered(&mut l, &mut r));
+        let r = input.parse::<Packet>().unwrap();
+        assert!(l < r);
     }
 }
@@ -0,0 +1,79 @@
+use crate::day_13::{Item, Packet};
+
+/// `[[2]]` and `[[6]]`
+pub(crate) fn divider_packets() -> [Packet; 2] {
+    [2, 6].map(|n| vec![Item::List(vec![Item::Int(n)])].into())
+}
+
+/// 1-based index `packet` would get among `packets` once sorted, without sorting them.
+/// Packets equal to it do not count as placed before
+pub(crate) fn sorted_index<T: Ord>(packets: &[T], packet: &T) -> usize {
+    1 + packets.iter().filter(|other| *other < packet).count()
+}
+
+/// Product of the indices of both dividers, once added to `packets` and everything sorted
+pub(crate) fn decoder_key<T: Ord>(packets: &[T], dividers: &[T; 2]) -> usize {
+    dividers
+        .iter()
+        .map(|divider| sorted_index(packets, divider) + sorted_index(dividers, divider) - 1)
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_13::part_2::{decoder_key, divider_packets, sorted_index};
+    use crate::day_13::tests::{puzzle_input, sample_pairs};
+    use crate::day_13::Packet;
+
+    fn packets(pairs: Vec<(Packet, Packet)>) -> Vec<Packet> {
+        pairs
+            .into_iter()
+            .flat_map(|(lhs, rhs)| [lhs, rhs])
+            .collect()
+    }
+
+    #[test]
+    fn check_sample() {
+        let mut packets = packets(sample_pairs());
+        let dividers = divider_packets();
+        assert_eq!(140, decoder_key(&packets, &dividers));
+
+        packets.extend(dividers);
+        let unsorted = packets.clone();
+        packets.sort_unstable();
+        for packet in unsorted.iter() {
+            assert_eq!(packet, &packets[sorted_index(&unsorted, packet) - 1]);
+        }
+        let expected = [
+            "[]",
+            "[[]]",
+            "[[[]]]",
+            "[1,1,3,1,1]",
+            "[1,1,5,1,1]",
+            "[[1],[2,3,4]]",
+            "[1,[2,[3,[4,[5,6,0]]]],8,9]",
+            "[1,[2,[3,[4,[5,6,7]]]],8,9]",
+            "[[1],4]",
+            "[[2]]",
+            "[3]",
+            "[[4,4],4,4]",
+            "[[4,4],4,4,4]",
+            "[[6]]",
+            "[7,7,7]",
+            "[7,7,7,7]",
+            "[[8,7,6]]",
+            "[9]",
+        ]
+        .map(|packet| packet.parse::<Packet>().unwrap().to_string());
+        assert_eq!(
+            expected.to_vec(),
+            packets.iter().map(Packet::to_string).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn solution() {
+        let packets = packets(puzzle_input());
+        assert_eq!(22464, decoder_key(&packets, &divider_packets()));
+    }
+}
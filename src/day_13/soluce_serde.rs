@@ -1,3 +1,4 @@
+use crate::day_13::{Item, Packet};
 use serde::Deserialize;
 use std::cmp::Ordering;
 use std::fmt;
@@ -18,6 +19,21 @@ impl fmt::Debug for Node {
     }
 }
 
+impl From<&Item> for Node {
+    fn from(item: &Item) -> Self {
+        match item {
+            Item::Int(n) => Node::Number(*n),
+            Item::List(items) => Node::List(items.iter().map(Node::from).collect()),
+        }
+    }
+}
+
+impl From<&Packet> for Node {
+    fn from(packet: &Packet) -> Self {
+        Node::List(packet.items.iter().map(Node::from).collect())
+    }
+}
+
 impl Node {
     fn with_slice<T>(&self, f: impl FnOnce(&[Node]) -> T) -> T {
         match self {
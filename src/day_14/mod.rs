@@ -0,0 +1,232 @@
+mod part_1;
+mod part_2;
+
+use crate::day_14::part_1::sand_before_abyss;
+use crate::day_14::part_2::sand_until_blocked;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+use crate::utils::geom::{BoundingBox, Coord, Direction8};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Where sand pours from
+pub(crate) const SOURCE: Coord = Coord::new(500, 0);
+
+/// Where a falling unit of sand tries to go, in that order
+const FALLS: [Direction8; 3] = [Direction8::S, Direction8::SW, Direction8::SE];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tile {
+    Rock,
+    Sand,
+}
+
+/// Only what's not air is stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Cave {
+    tiles: HashMap<Coord, Tile>,
+    /// Row of the lowest rock, sand going below it falls forever, unless there's a floor
+    lowest_rock: i64,
+    /// Row of the infinite floor, if any
+    floor: Option<i64>,
+}
+
+/// `x,y` corners, the rock going straight from one to the next
+pub(crate) fn parse_path(line: &str) -> Result<Vec<Coord>, String> {
+    line.split(" -> ")
+        .map(|corner| {
+            let (x, y) = corner
+                .split_once(',')
+                .ok_or(format!("Invalid corner {corner:?}"))?;
+            let coord = |n: &str| {
+                n.trim()
+                    .parse::<i64>()
+                    .map_err(|_| format!("Invalid corner {corner:?}"))
+            };
+            Ok(Coord::new(coord(x)?, coord(y)?))
+        })
+        .collect()
+}
+
+impl Cave {
+    pub(crate) fn new(paths: &[Vec<Coord>]) -> Result<Self, String> {
+        let mut tiles = HashMap::new();
+        for path in paths {
+            if let [single] = path.as_slice() {
+                tiles.insert(*single, Tile::Rock);
+            }
+            for segment in path.windows(2) {
+                let (from, to) = (segment[0], segment[1]);
+                if from.x != to.x && from.y != to.y {
+                    return Err(format!("Rock from {from:?} to {to:?} is not straight"));
+                }
+                let step = (to - from).signum();
+                let mut current = from;
+                tiles.insert(current, Tile::Rock);
+                while current != to {
+                    current += step;
+                    tiles.insert(current, Tile::Rock);
+                }
+            }
+        }
+        let lowest_rock = tiles
+            .keys()
+            .map(|coord| coord.y)
+            .max()
+            .ok_or("There's no rock")?;
+        Ok(Cave {
+            tiles,
+            lowest_rock,
+            floor: None,
+        })
+    }
+
+    /// The same cave, on an infinite floor two rows below its lowest rock
+    pub(crate) fn with_floor(mut self) -> Self {
+        self.floor = Some(self.lowest_rock + 2);
+        self
+    }
+
+    pub(crate) fn get(&self, coord: &Coord) -> Option<Tile> {
+        match self.floor {
+            Some(floor) if coord.y == floor => Some(Tile::Rock),
+            _ => self.tiles.get(coord).copied(),
+        }
+    }
+
+    pub(crate) fn sand(&self) -> usize {
+        self.tiles.values().filter(|t| **t == Tile::Sand).count()
+    }
+
+    /// Pours a unit of sand from `SOURCE`, where it comes to rest.
+    /// `None` when it falls into the abyss, or when the source is already blocked
+    pub(crate) fn drop_sand(&mut self) -> Option<Coord> {
+        if self.get(&SOURCE).is_some() {
+            return None;
+        }
+        let mut sand = SOURCE;
+        loop {
+            if self.floor.is_none() && sand.y > self.lowest_rock {
+                return None;
+            }
+            let next = FALLS
+                .iter()
+                .map(|fall| sand + fall.delta())
+                .find(|next| self.get(next).is_none());
+            match next {
+                Some(next) => sand = next,
+                None => {
+                    self.tiles.insert(sand, Tile::Sand);
+                    return Some(sand);
+                }
+            }
+        }
+    }
+}
+
+/// `#` for rock, `o` for sand and `+` for the source, around everything but the floor
+impl Display for Cave {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut bbox = BoundingBox::new(SOURCE);
+        for coord in self.tiles.keys() {
+            bbox.extend(coord);
+        }
+        if let Some(floor) = self.floor {
+            bbox.extend(&Coord::new(bbox.min.x, floor));
+        }
+        for y in bbox.min.y..=bbox.max.y {
+            for x in bbox.min.x..=bbox.max.x {
+                let coord = Coord::new(x, y);
+                let tile = match self.get(&coord) {
+                    Some(Tile::Rock) => '#',
+                    Some(Tile::Sand) => 'o',
+                    None if coord == SOURCE => '+',
+                    None => '.',
+                };
+                write!(f, "{tile}")?;
+            }
+            if y < bbox.max.y {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u8 = 14;
+    type Input = Cave;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let paths = parse_lines(input, parse_path)?;
+        Cave::new(&paths).map_err(ParseError::new)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(sand_before_abyss(input.clone()))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(sand_until_blocked(input.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_14::{parse_path, Cave, Tile};
+    use crate::utils::geom::Coord;
+
+    pub(crate) const SAMPLE: &str = "\
+498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+    pub(crate) fn sample() -> Cave {
+        let paths = SAMPLE
+            .lines()
+            .map(|line| parse_path(line).unwrap())
+            .collect::<Vec<_>>();
+        Cave::new(&paths).unwrap()
+    }
+
+    #[test]
+    fn parses_rock_paths() {
+        assert_eq!(
+            Ok(vec![Coord::new(498, 4), Coord::new(498, 6)]),
+            parse_path("498,4 -> 498,6")
+        );
+        assert!(parse_path("498,4 -> 498").is_err());
+        assert!(Cave::new(&[vec![Coord::new(0, 0), Coord::new(1, 1)]]).is_err());
+        let cave = sample();
+        assert_eq!(Some(Tile::Rock), cave.get(&Coord::new(497, 6)));
+        assert_eq!(None, cave.get(&Coord::new(497, 5)));
+    }
+
+    #[test]
+    fn renders_the_cave() {
+        let expected = "\
+......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########.";
+        assert_eq!(expected, sample().to_string());
+    }
+
+    #[test]
+    fn sand_comes_to_rest() {
+        let mut cave = sample();
+        assert_eq!(Some(Coord::new(500, 8)), cave.drop_sand());
+        assert_eq!(Some(Coord::new(499, 8)), cave.drop_sand());
+        assert_eq!(Some(Coord::new(501, 8)), cave.drop_sand());
+        assert_eq!(3, cave.sand());
+    }
+}
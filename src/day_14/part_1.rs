@@ -0,0 +1,36 @@
+use crate::day_14::Cave;
+
+/// Units of sand resting once the next one falls into the abyss
+pub(crate) fn sand_before_abyss(mut cave: Cave) -> usize {
+    while cave.drop_sand().is_some() {}
+    cave.sand()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_14::part_1::sand_before_abyss;
+    use crate::day_14::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(24, sand_before_abyss(sample()));
+    }
+
+    #[test]
+    fn renders_resting_sand() {
+        let mut cave = sample();
+        while cave.drop_sand().is_some() {}
+        let expected = "\
+......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.";
+        assert_eq!(expected, cave.to_string());
+    }
+}
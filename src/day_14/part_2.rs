@@ -0,0 +1,42 @@
+use crate::day_14::Cave;
+
+/// Units of sand resting on the floor, or on each other, once the source is blocked
+pub(crate) fn sand_until_blocked(cave: Cave) -> usize {
+    let mut cave = cave.with_floor();
+    while cave.drop_sand().is_some() {}
+    cave.sand()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_14::part_2::sand_until_blocked;
+    use crate::day_14::tests::sample;
+    use crate::day_14::SOURCE;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(93, sand_until_blocked(sample()));
+    }
+
+    #[test]
+    fn source_ends_blocked() {
+        let mut cave = sample().with_floor();
+        while cave.drop_sand().is_some() {}
+        assert!(cave.get(&SOURCE).is_some());
+        assert_eq!(None, cave.drop_sand());
+        let expected = "\
+..........o..........
+.........ooo.........
+........ooooo........
+.......ooooooo.......
+......oo#ooo##o......
+.....ooo#ooo#ooo.....
+....oo###ooo#oooo....
+...oooo.oooo#ooooo...
+..oooooooooo#oooooo..
+.ooo#########ooooooo.
+ooooo.......ooooooooo
+#####################";
+        assert_eq!(expected, cave.to_string());
+    }
+}
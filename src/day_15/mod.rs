@@ -0,0 +1,152 @@
+mod part_1;
+mod part_2;
+
+use crate::day_15::part_1::positions_without_beacon;
+use crate::day_15::part_2::{distress_beacon, tuning_frequency};
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+use crate::utils::geom::Coord;
+use crate::utils::ranges::RangeSet;
+use nom::bytes::complete::tag;
+use nom::character::complete::i64 as i64p;
+use nom::combinator::{all_consuming, map};
+use nom::sequence::{preceded, separated_pair, tuple};
+use nom::IResult;
+use std::ops::RangeInclusive;
+
+/// Row looked at in part 1
+pub(crate) const ROW: i64 = 2_000_000;
+/// The distress beacon has both coordinates between 0 and this
+pub(crate) const SEARCH_LIMIT: i64 = 4_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Sensor {
+    pub(crate) position: Coord,
+    /// The closest one, no other beacon can be as close
+    pub(crate) beacon: Coord,
+}
+
+impl Sensor {
+    pub(crate) fn radius(&self) -> i64 {
+        self.position.manhattan(&self.beacon)
+    }
+
+    pub(crate) fn covers(&self, coord: &Coord) -> bool {
+        self.position.manhattan(coord) <= self.radius()
+    }
+
+    /// Columns of `row` at most as far from the sensor as its beacon
+    pub(crate) fn covered(&self, row: i64) -> Option<RangeInclusive<i64>> {
+        let half_width = self.radius() - (row - self.position.y).abs();
+        (half_width >= 0).then(|| self.position.x - half_width..=self.position.x + half_width)
+    }
+}
+
+/// Columns of `row` covered by any of the sensors
+pub(crate) fn coverage(sensors: &[Sensor], row: i64) -> RangeSet {
+    sensors.iter().filter_map(|s| s.covered(row)).collect()
+}
+
+fn parse_coord(input: &str) -> IResult<&str, Coord> {
+    map(
+        separated_pair(
+            preceded(tag("x="), i64p),
+            tag(", "),
+            preceded(tag("y="), i64p),
+        ),
+        |(x, y)| Coord::new(x, y),
+    )(input)
+}
+
+pub(crate) fn parse_sensor(line: &str) -> IResult<&str, Sensor> {
+    map(
+        tuple((
+            tag("Sensor at "),
+            parse_coord,
+            tag(": closest beacon is at "),
+            parse_coord,
+        )),
+        |(_, position, _, beacon)| Sensor { position, beacon },
+    )(line)
+}
+
+pub(crate) struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<Sensor>;
+    type Answer1 = u64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| {
+            all_consuming(parse_sensor)(line)
+                .map(|(_, sensor)| sensor)
+                .map_err(|_| format!("Invalid sensor {line:?}"))
+        })
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(positions_without_beacon(input, ROW))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        distress_beacon(input, SEARCH_LIMIT)
+            .map(|beacon| tuning_frequency(&beacon))
+            .ok_or_else(|| SolveError::NoSolution("Every position is covered".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_15::{coverage, parse_sensor, Sensor};
+    use crate::utils::geom::Coord;
+
+    pub(crate) const SAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    pub(crate) fn sample() -> Vec<Sensor> {
+        SAMPLE
+            .lines()
+            .map(|line| parse_sensor(line).unwrap().1)
+            .collect()
+    }
+
+    #[test]
+    fn parses_sensors() {
+        assert_eq!(
+            Sensor {
+                position: Coord::new(2, 18),
+                beacon: Coord::new(-2, 15)
+            },
+            sample()[0]
+        );
+        assert!(parse_sensor("Sensor at x=2: closest beacon is at x=-2, y=15").is_err());
+    }
+
+    #[test]
+    fn covers_diamonds() {
+        let sensor = &sample()[6];
+        assert_eq!(9, sensor.radius());
+        assert_eq!(Some(8..=8), sensor.covered(-2));
+        assert_eq!(Some(-1..=17), sensor.covered(7));
+        assert_eq!(None, sensor.covered(17));
+        assert!(sensor.covers(&Coord::new(2, 10)));
+        assert!(!sensor.covers(&Coord::new(2, 11)));
+        let covered = coverage(&sample(), 10);
+        assert_eq!(27, covered.len());
+        assert_eq!(vec![-3..=-3, 25..=25], covered.gaps(-3..=25));
+    }
+}
@@ -0,0 +1,25 @@
+use crate::day_15::{coverage, Sensor};
+use std::collections::HashSet;
+
+/// Positions of `row` where no beacon can be, the known ones aside
+pub(crate) fn positions_without_beacon(sensors: &[Sensor], row: i64) -> u64 {
+    let covered = coverage(sensors, row);
+    let beacons = sensors
+        .iter()
+        .map(|sensor| sensor.beacon)
+        .filter(|beacon| beacon.y == row && covered.contains(beacon.x))
+        .collect::<HashSet<_>>();
+    covered.len() - beacons.len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_15::part_1::positions_without_beacon;
+    use crate::day_15::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(26, positions_without_beacon(&sample(), 10));
+        assert_eq!(0, positions_without_beacon(&sample(), 1_000));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::day_15::{coverage, Sensor};
+use crate::utils::geom::Coord;
+use std::collections::HashSet;
+
+pub(crate) fn tuning_frequency(beacon: &Coord) -> i64 {
+    beacon.x * 4_000_000 + beacon.y
+}
+
+fn uncovered(sensors: &[Sensor], limit: i64, coord: &Coord) -> bool {
+    (0..=limit).contains(&coord.x)
+        && (0..=limit).contains(&coord.y)
+        && sensors.iter().all(|sensor| !sensor.covers(coord))
+}
+
+/// A single position being left, it lies right outside of at least two sensor ranges, where their
+/// borders cross (unless it's on the edge of the search area). Borders follow `y = x + a` or
+/// `y = -x + b`, intersections are checked as long as their coordinates are integers
+fn border_crossings(sensors: &[Sensor]) -> Vec<Coord> {
+    let mut ascending = HashSet::new();
+    let mut descending = HashSet::new();
+    for sensor in sensors {
+        let (position, reach) = (sensor.position, sensor.radius() + 1);
+        ascending.extend([
+            position.y - position.x - reach,
+            position.y - position.x + reach,
+        ]);
+        descending.extend([
+            position.y + position.x - reach,
+            position.y + position.x + reach,
+        ]);
+    }
+    let mut crossings = vec![];
+    for a in &ascending {
+        for b in descending.iter().filter(|b| (*b - a) % 2 == 0) {
+            crossings.push(Coord::new((b - a) / 2, (a + b) / 2));
+        }
+    }
+    crossings
+}
+
+/// Merges what every row has covered, until one of them has a gap: slower, but makes no assumption
+pub(crate) fn scan_rows(sensors: &[Sensor], limit: i64) -> Option<Coord> {
+    (0..=limit).find_map(|y| {
+        coverage(sensors, y)
+            .gaps(0..=limit)
+            .first()
+            .map(|gap| Coord::new(*gap.start(), y))
+    })
+}
+
+/// The only position, both coordinates between 0 and `limit`, no sensor covers
+pub(crate) fn distress_beacon(sensors: &[Sensor], limit: i64) -> Option<Coord> {
+    border_crossings(sensors)
+        .into_iter()
+        .find(|coord| uncovered(sensors, limit, coord))
+        .or_else(|| scan_rows(sensors, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_15::part_2::{distress_beacon, scan_rows, tuning_frequency};
+    use crate::day_15::tests::sample;
+    use crate::day_15::Sensor;
+    use crate::utils::geom::Coord;
+
+    #[test]
+    fn check_sample() {
+        let beacon = distress_beacon(&sample(), 20).unwrap();
+        assert_eq!(Coord::new(14, 11), beacon);
+        assert_eq!(56000011, tuning_frequency(&beacon));
+        assert_eq!(Some(beacon), scan_rows(&sample(), 20));
+    }
+
+    #[test]
+    fn finds_beacons_in_corners() {
+        // covering everything but (0, 0), which lies on no border crossing
+        let sensors = vec![Sensor {
+            position: Coord::new(2, 2),
+            beacon: Coord::new(1, 0),
+        }];
+        assert_eq!(Some(Coord::new(0, 0)), distress_beacon(&sensors, 3));
+    }
+}
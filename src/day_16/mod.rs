@@ -0,0 +1,309 @@
+use crate::day_16::parse::parse_all_valves_def;
+use crate::day_16::part_1::best_plan;
+use crate::day_16::part_2::best_pair;
+use crate::solver::{line_of, ParseError, SolveError, Solver};
+use std::collections::HashMap;
+
+mod parse;
+mod part_1;
+mod part_2;
+
+/// Where every walk starts
+pub(crate) const START: &str = "AA";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Valve {
+    pub(crate) name: String,
+    pub(crate) flow_rate: u64,
+    pub(crate) tunnels: Vec<String>,
+}
+
+/// A valve opened during the given minute (the first one being 1), releasing pressure from the next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Opening {
+    pub(crate) minute: u64,
+    pub(crate) valve: String,
+}
+
+/// Pressure released by the time is up, opening valves as scheduled
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Plan {
+    pub(crate) pressure: u64,
+    pub(crate) schedule: Vec<Opening>,
+}
+
+/// Valves with some flow only, and the time it takes to walk from one to another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Network {
+    names: Vec<String>,
+    flow_rates: Vec<u64>,
+    /// `distances[i][j]` minutes to walk from useful valve `i` to useful valve `j`
+    distances: Vec<Vec<u64>>,
+    /// Minutes to walk from `START` to each useful valve
+    from_start: Vec<u64>,
+}
+
+/// Floyd-Warshall: `None` when there's no way from a valve to the other
+fn all_pairs_shortest_paths(valves: &[Valve]) -> Result<Vec<Vec<Option<u64>>>, String> {
+    let index = valves
+        .iter()
+        .enumerate()
+        .map(|(i, valve)| (valve.name.as_str(), i))
+        .collect::<HashMap<_, _>>();
+    let n = valves.len();
+    let mut distances = vec![vec![None; n]; n];
+    for (i, valve) in valves.iter().enumerate() {
+        distances[i][i] = Some(0);
+        for tunnel in &valve.tunnels {
+            let j = *index.get(tunnel.as_str()).ok_or(format!(
+                "Valve {} leads to unknown valve {tunnel}",
+                valve.name
+            ))?;
+            distances[i][j] = Some(1);
+        }
+    }
+    for k in 0..n {
+        let through = distances[k].clone();
+        for from in distances.iter_mut() {
+            let Some(ik) = from[k] else {
+                continue;
+            };
+            for (ij, kj) in from.iter_mut().zip(&through) {
+                if let Some(kj) = kj {
+                    if ij.is_none_or(|ij| ik + kj < ij) {
+                        *ij = Some(ik + kj);
+                    }
+                }
+            }
+        }
+    }
+    Ok(distances)
+}
+
+impl Network {
+    /// Valves out of reach from `START` are left aside, as are the ones with no flow
+    pub(crate) fn new(valves: &[Valve]) -> Result<Self, String> {
+        let distances = all_pairs_shortest_paths(valves)?;
+        let start = valves
+            .iter()
+            .position(|valve| valve.name == START)
+            .ok_or(format!("There's no valve {START}"))?;
+        let useful = (0..valves.len())
+            .filter(|i| valves[*i].flow_rate > 0 && distances[start][*i].is_some())
+            .collect::<Vec<_>>();
+        // opened valves are kept in a bit mask
+        if useful.len() > 64 {
+            return Err(format!(
+                "{} valves with some flow, at most 64 are handled",
+                useful.len()
+            ));
+        }
+        Ok(Network {
+            names: useful.iter().map(|i| valves[*i].name.clone()).collect(),
+            flow_rates: useful.iter().map(|i| valves[*i].flow_rate).collect(),
+            distances: useful
+                .iter()
+                .map(|i| {
+                    useful
+                        .iter()
+                        .map(|j| distances[*i][*j].unwrap_or(u64::MAX))
+                        .collect()
+                })
+                .collect(),
+            from_start: useful
+                .iter()
+                .map(|i| distances[start][*i].unwrap())
+                .collect(),
+        })
+    }
+
+    /// The best plan for every set of valves (as a bit mask of useful valves) that can be opened in time
+    pub(crate) fn plans(&self, minutes: u64) -> HashMap<u64, Plan> {
+        let mut search = Search {
+            network: self,
+            minutes,
+            walk: vec![],
+            plans: HashMap::new(),
+        };
+        search.explore(State {
+            at: None,
+            minutes_left: minutes,
+            opened: 0,
+            pressure: 0,
+        });
+        search.plans
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    /// `None` being `START`
+    at: Option<usize>,
+    minutes_left: u64,
+    /// Bit mask of useful valves
+    opened: u64,
+    /// Released by the time is up, by the valves opened so far
+    pressure: u64,
+}
+
+struct Search<'a> {
+    network: &'a Network,
+    minutes: u64,
+    /// The (minute, valve) openings so far
+    walk: Vec<(u64, usize)>,
+    /// The best plan for every set of valves opened so far
+    plans: HashMap<u64, Plan>,
+}
+
+impl Search<'_> {
+    /// Depth-first, walking to each valve not opened yet
+    fn explore(&mut self, state: State) {
+        let best = self.plans.entry(state.opened).or_default();
+        if best.schedule.is_empty() && state.opened != 0 || best.pressure < state.pressure {
+            *best = Plan {
+                pressure: state.pressure,
+                schedule: self
+                    .walk
+                    .iter()
+                    .map(|(minute, valve)| Opening {
+                        minute: *minute,
+                        valve: self.network.names[*valve].clone(),
+                    })
+                    .collect(),
+            };
+        }
+        let network = self.network;
+        for next in 0..network.names.len() {
+            if state.opened & (1 << next) != 0 {
+                continue;
+            }
+            let distance = match state.at {
+                Some(at) => network.distances[at][next],
+                None => network.from_start[next],
+            };
+            // walking there, then a minute to open it, leaving at least a minute for the pressure
+            let minutes = distance.saturating_add(1);
+            if minutes >= state.minutes_left {
+                continue;
+            }
+            let minutes_left = state.minutes_left - minutes;
+            self.walk.push((self.minutes - minutes_left, next));
+            self.explore(State {
+                at: Some(next),
+                minutes_left,
+                opened: state.opened | (1 << next),
+                pressure: state.pressure + network.flow_rates[next] * minutes_left,
+            });
+            self.walk.pop();
+        }
+    }
+}
+
+pub(crate) struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u8 = 16;
+    type Input = Network;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let (rest, valves) = parse_all_valves_def(input)
+            .map_err(|e| ParseError::new(format!("Invalid valve definitions: {e}")))?;
+        if !rest.trim().is_empty() {
+            return Err(ParseError::at_line(
+                line_of(input, rest),
+                "Invalid valve definition",
+            ));
+        }
+        Network::new(&valves).map_err(ParseError::new)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(best_plan(input, 30).pressure)
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        let (me, elephant) = best_pair(input, 26);
+        Ok(me.pressure + elephant.pressure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_16::parse::parse_all_valves_def;
+    use crate::day_16::{Day16, Network, Valve, START};
+    use crate::solver::Solver;
+
+    pub(crate) const SAMPLE: &str = "\
+Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+    pub(crate) fn sample() -> Network {
+        Network::new(&parse_all_valves_def(SAMPLE).unwrap().1).unwrap()
+    }
+
+    #[test]
+    fn keeps_useful_valves_only() {
+        let network = sample();
+        assert_eq!(vec!["BB", "CC", "DD", "EE", "HH", "JJ"], network.names);
+        assert_eq!(vec![13, 2, 20, 3, 22, 21], network.flow_rates);
+        // BB to DD, JJ to HH
+        assert_eq!(2, network.distances[0][2]);
+        assert_eq!(7, network.distances[5][4]);
+        assert_eq!(0, network.distances[3][3]);
+        assert_eq!(vec![1, 2, 1, 2, 5, 2], network.from_start);
+    }
+
+    #[test]
+    fn rejects_broken_networks() {
+        let (_, valves) =
+            parse_all_valves_def("Valve AA has flow rate=0; tunnel leads to valve BB").unwrap();
+        assert!(Network::new(&valves).is_err());
+        let (_, valves) =
+            parse_all_valves_def("Valve BB has flow rate=0; tunnel leads to valve BB").unwrap();
+        assert!(Network::new(&valves).is_err());
+    }
+
+    #[test]
+    fn reports_the_line_of_an_invalid_valve() {
+        let input = SAMPLE.replace("Valve CC has flow rate=2", "Valve CC has flow rate=two");
+        assert_eq!(Some(3), Day16.parse(&input).unwrap_err().line);
+    }
+
+    /// `AA` then `count` valves, each one leading to the next, the last one having some flow
+    fn corridor(count: usize, flow_rate: impl Fn(usize) -> u64) -> Vec<Valve> {
+        let name = |i: usize| match i {
+            0 => START.to_string(),
+            i => format!("V{i}"),
+        };
+        (0..=count)
+            .map(|i| Valve {
+                name: name(i),
+                flow_rate: if i == 0 { 0 } else { flow_rate(i) },
+                tunnels: [i.checked_sub(1), (i < count).then_some(i + 1)]
+                    .into_iter()
+                    .flatten()
+                    .map(name)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn limits_useful_valves_only() {
+        let network = Network::new(&corridor(100, |i| (i == 100) as u64 * 7)).unwrap();
+        assert_eq!(vec!["V100"], network.names);
+        assert_eq!(vec![100], network.from_start);
+        assert!(Network::new(&corridor(64, |_| 1)).is_ok());
+        assert!(Network::new(&corridor(65, |_| 1)).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::day_16::Valve;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, line_ending, u64 as u64p};
+use nom::combinator::map;
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::tuple;
+use nom::IResult;
+
+fn parse_name(def: &str) -> IResult<&str, String> {
+    map(alpha1, str::to_string)(def)
+}
+
+fn parse_tunnels(def: &str) -> IResult<&str, Vec<String>> {
+    let (rest, _) = alt((
+        tag("tunnels lead to valves "),
+        tag("tunnel leads to valve "),
+    ))(def)?;
+    separated_list1(tag(", "), parse_name)(rest)
+}
+
+pub(crate) fn parse_valve_def(def: &str) -> IResult<&str, Valve> {
+    let (rest, (_, name, _, flow_rate, _, tunnels)) = tuple((
+        tag("Valve "),
+        parse_name,
+        tag(" has flow rate="),
+        u64p,
+        tag("; "),
+        parse_tunnels,
+    ))(def)?;
+    Ok((
+        rest,
+        Valve {
+            name,
+            flow_rate,
+            tunnels,
+        },
+    ))
+}
+
+pub(crate) fn parse_all_valves_def(input: &str) -> IResult<&str, Vec<Valve>> {
+    separated_list0(line_ending, parse_valve_def)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_16::parse::{parse_all_valves_def, parse_valve_def};
+    use crate::day_16::tests::SAMPLE;
+    use crate::day_16::Valve;
+
+    #[test]
+    fn can_parse_valve() {
+        let (rest, valve) =
+            parse_valve_def("Valve AA has flow rate=0; tunnels lead to valves DD, II, BB").unwrap();
+        assert_eq!("", rest);
+        assert_eq!(
+            Valve {
+                name: "AA".to_string(),
+                flow_rate: 0,
+                tunnels: vec!["DD".to_string(), "II".to_string(), "BB".to_string()],
+            },
+            valve
+        );
+        let (_, valve) =
+            parse_valve_def("Valve HH has flow rate=22; tunnel leads to valve GG").unwrap();
+        assert_eq!(vec!["GG".to_string()], valve.tunnels);
+        assert!(parse_valve_def("Valve HH has flow rate=22; tunnel leads to").is_err());
+    }
+
+    #[test]
+    fn can_parse_sample() {
+        let (rest, valves) = parse_all_valves_def(SAMPLE).unwrap();
+        assert_eq!("", rest);
+        assert_eq!(10, valves.len());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::day_16::{Network, Plan};
+
+/// Most pressure released alone in `minutes`
+pub(crate) fn best_plan(network: &Network, minutes: u64) -> Plan {
+    network
+        .plans(minutes)
+        .into_values()
+        .max_by_key(|plan| plan.pressure)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_16::part_1::best_plan;
+    use crate::day_16::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        let plan = best_plan(&sample(), 30);
+        assert_eq!(1651, plan.pressure);
+        let schedule = plan
+            .schedule
+            .iter()
+            .map(|opening| (opening.minute, opening.valve.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (2, "DD"),
+                (5, "BB"),
+                (9, "JJ"),
+                (17, "HH"),
+                (21, "EE"),
+                (24, "CC")
+            ],
+            schedule
+        );
+    }
+
+    #[test]
+    fn no_time_no_pressure() {
+        assert_eq!(0, best_plan(&sample(), 1).pressure);
+        assert!(best_plan(&sample(), 2).schedule.is_empty());
+        assert_eq!(20, best_plan(&sample(), 3).pressure);
+    }
+}
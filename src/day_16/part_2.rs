@@ -0,0 +1,61 @@
+use crate::day_16::{Network, Plan};
+use std::cmp::Reverse;
+
+/// Most pressure released in `minutes` by two walkers opening different valves: yours, then the elephant's
+pub(crate) fn best_pair(network: &Network, minutes: u64) -> (Plan, Plan) {
+    let mut plans = network.plans(minutes).into_iter().collect::<Vec<_>>();
+    plans.sort_unstable_by_key(|(_, plan)| Reverse(plan.pressure));
+    let mut best: Option<(usize, usize)> = None;
+    let mut best_pressure = 0;
+    for (i, (mine, plan)) in plans.iter().enumerate() {
+        // sorted: every other pair from here on releases less
+        if plan.pressure * 2 < best_pressure {
+            break;
+        }
+        for (j, (its, other)) in plans.iter().enumerate().skip(i) {
+            let pressure = plan.pressure + other.pressure;
+            if pressure <= best_pressure && best.is_some() {
+                break;
+            }
+            if mine & its == 0 {
+                best_pressure = pressure;
+                best = Some((i, j));
+                break;
+            }
+        }
+    }
+    match best {
+        Some((i, j)) => (plans[i].1.clone(), plans[j].1.clone()),
+        None => (Plan::default(), Plan::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_16::part_1::best_plan;
+    use crate::day_16::part_2::best_pair;
+    use crate::day_16::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        let (me, elephant) = best_pair(&sample(), 26);
+        assert_eq!(1707, me.pressure + elephant.pressure);
+        let mut opened = me
+            .schedule
+            .iter()
+            .chain(elephant.schedule.iter())
+            .map(|opening| opening.valve.as_str())
+            .collect::<Vec<_>>();
+        opened.sort_unstable();
+        assert_eq!(vec!["BB", "CC", "DD", "EE", "HH", "JJ"], opened);
+    }
+
+    #[test]
+    fn never_worse_than_alone() {
+        let network = sample();
+        for minutes in [0, 5, 10, 20] {
+            let (me, elephant) = best_pair(&network, minutes);
+            assert!(me.pressure + elephant.pressure >= best_plan(&network, minutes).pressure);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+mod part_1;
+mod part_2;
+
+use crate::day_17::part_1::tower_height_after;
+use crate::day_17::part_2::tower_height;
+use crate::solver::{ParseError, SolveError, Solver};
+use crate::utils::geom::Direction;
+use crate::utils::geom::Direction::{Left, Right};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Columns of the chamber
+const WIDTH: usize = 7;
+
+/// One bit per column, the leftmost one being the highest bit
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+
+/// Rows of each rock from the bottom, as they appear: two units away from the left wall
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+/// Jets push rocks either to the left (`<`) or to the right (`>`)
+fn jet(ch: char) -> Result<Direction, String> {
+    match ch {
+        '<' => Ok(Left),
+        '>' => Ok(Right),
+        _ => Err(format!("Unknown jet {ch:?}")),
+    }
+}
+
+/// Rocks piling up, one row per bit mask from the floor upwards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Chamber<'a> {
+    rows: Vec<u8>,
+    jets: &'a [Direction],
+    /// Index of the next jet
+    jet: usize,
+    rocks: u64,
+}
+
+/// Where the falling `rock` would end up pushed to `direction`, `None` if a wall is in the way
+fn pushed(rock: &[u8], direction: Direction) -> Option<Vec<u8>> {
+    match direction {
+        Left if rock.iter().all(|row| row & LEFT_WALL == 0) => {
+            Some(rock.iter().map(|row| row << 1).collect())
+        }
+        Right if rock.iter().all(|row| row & RIGHT_WALL == 0) => {
+            Some(rock.iter().map(|row| row >> 1).collect())
+        }
+        _ => None,
+    }
+}
+
+impl<'a> Chamber<'a> {
+    pub(crate) fn new(jets: &'a [Direction]) -> Self {
+        Chamber {
+            rows: vec![],
+            jets,
+            jet: 0,
+            rocks: 0,
+        }
+    }
+
+    pub(crate) fn height(&self) -> u64 {
+        self.rows.len() as u64
+    }
+
+    /// Rocks that came to rest so far
+    pub(crate) fn rocks(&self) -> u64 {
+        self.rocks
+    }
+
+    /// Whether `rock` overlaps nothing, its bottom row at `y`
+    fn fits(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter().enumerate().all(|(i, row)| {
+            self.rows
+                .get(y + i)
+                .is_none_or(|settled| settled & row == 0)
+        })
+    }
+
+    /// Lets the next rock fall, pushed by jets, until it comes to rest
+    pub(crate) fn drop_rock(&mut self) {
+        let mut rock = ROCKS[(self.rocks % ROCKS.len() as u64) as usize].to_vec();
+        let mut y = self.rows.len() + 3;
+        loop {
+            let direction = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            if let Some(moved) = pushed(&rock, direction) {
+                if self.fits(&moved, y) {
+                    rock = moved;
+                }
+            }
+            if y == 0 || !self.fits(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for (i, row) in rock.iter().enumerate() {
+            match self.rows.get_mut(y + i) {
+                Some(settled) => *settled |= row,
+                None => self.rows.push(*row),
+            }
+        }
+        self.rocks += 1;
+    }
+
+    /// What the next rocks will do depends on this only: the next rock and jet,
+    /// and how deep each column is from the top of the tower
+    pub(crate) fn state(&self) -> (usize, usize, [u64; WIDTH]) {
+        let mut depths = [self.height(); WIDTH];
+        for (column, depth) in depths.iter_mut().enumerate() {
+            let bit = LEFT_WALL >> column;
+            if let Some(top) = self.rows.iter().rposition(|row| row & bit != 0) {
+                *depth = self.height() - top as u64 - 1;
+            }
+        }
+        ((self.rocks % ROCKS.len() as u64) as usize, self.jet, depths)
+    }
+}
+
+/// Rows from the top, `#` for rock, down to the floor
+impl Display for Chamber<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows.iter().rev() {
+            write!(f, "|")?;
+            for column in 0..WIDTH {
+                let tile = if row & (LEFT_WALL >> column) != 0 {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{tile}")?;
+            }
+            writeln!(f, "|")?;
+        }
+        write!(f, "+{}+", "-".repeat(WIDTH))
+    }
+}
+
+pub(crate) struct Day17;
+
+impl Solver for Day17 {
+    const DAY: u8 = 17;
+    type Input = Vec<Direction>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let jets = input
+            .trim()
+            .chars()
+            .enumerate()
+            .map(|(i, ch)| jet(ch).map_err(|e| ParseError::new(format!("{e} at column {}", i + 1))))
+            .collect::<Result<Vec<_>, _>>()?;
+        if jets.is_empty() {
+            return Err(ParseError::new("There's no jet"));
+        }
+        Ok(jets)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(tower_height_after(input, 2022))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(tower_height(input, 1_000_000_000_000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_17::{Chamber, Day17};
+    use crate::solver::Solver;
+    use crate::utils::geom::Direction;
+    use crate::utils::geom::Direction::{Left, Right};
+
+    pub(crate) const SAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    pub(crate) fn sample() -> Vec<Direction> {
+        Day17.parse(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parses_jets() {
+        assert_eq!(vec![Right, Left, Right], Day17.parse("><>\n").unwrap());
+        let err = Day17.parse("<<v>").unwrap_err();
+        assert!(err.to_string().contains("column 3"));
+        assert!(Day17.parse("").is_err());
+    }
+
+    #[test]
+    fn rocks_come_to_rest() {
+        let jets = sample();
+        let mut chamber = Chamber::new(&jets);
+        chamber.drop_rock();
+        assert_eq!("|..####.|\n+-------+", chamber.to_string());
+        chamber.drop_rock();
+        chamber.drop_rock();
+        let expected = "\
+|..#....|
+|..#....|
+|####...|
+|..###..|
+|...#...|
+|..####.|
++-------+";
+        assert_eq!(expected, chamber.to_string());
+        assert_eq!((3, 6), (chamber.rocks(), chamber.height()));
+    }
+}
@@ -0,0 +1,22 @@
+use crate::day_17::Chamber;
+use crate::utils::geom::Direction;
+
+/// Height of the tower once `rocks` rocks came to rest, simulating every one of them
+pub(crate) fn tower_height_after(jets: &[Direction], rocks: u64) -> u64 {
+    let mut chamber = Chamber::new(jets);
+    while chamber.rocks() < rocks {
+        chamber.drop_rock();
+    }
+    chamber.height()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_17::part_1::tower_height_after;
+    use crate::day_17::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(3068, tower_height_after(&sample(), 2022));
+    }
+}
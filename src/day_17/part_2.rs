@@ -0,0 +1,78 @@
+use crate::day_17::part_1::tower_height_after;
+use crate::day_17::Chamber;
+use crate::utils::geom::Direction;
+use std::collections::HashMap;
+
+/// Once `start` rocks came to rest, every `period` rocks make the tower `growth` higher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Cycle {
+    pub(crate) start: u64,
+    pub(crate) period: u64,
+    pub(crate) growth: u64,
+}
+
+/// Drops rocks until the chamber gets back to a state it has already been in
+pub(crate) fn find_cycle(jets: &[Direction]) -> Cycle {
+    let mut chamber = Chamber::new(jets);
+    let mut seen = HashMap::new();
+    loop {
+        chamber.drop_rock();
+        let now = (chamber.rocks(), chamber.height());
+        if let Some((rocks, height)) = seen.insert(chamber.state(), now) {
+            return Cycle {
+                start: rocks,
+                period: now.0 - rocks,
+                growth: now.1 - height,
+            };
+        }
+    }
+}
+
+/// Height of the tower once `rocks` rocks came to rest, skipping over as many cycles as possible
+pub(crate) fn tower_height(jets: &[Direction], rocks: u64) -> u64 {
+    let Cycle {
+        start,
+        period,
+        growth,
+    } = find_cycle(jets);
+    if rocks <= start {
+        return tower_height_after(jets, rocks);
+    }
+    let cycles = (rocks - start) / period;
+    tower_height_after(jets, start + (rocks - start) % period) + cycles * growth
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_17::part_1::tower_height_after;
+    use crate::day_17::part_2::{find_cycle, tower_height};
+    use crate::day_17::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(
+            1_514_285_714_288,
+            tower_height(&sample(), 1_000_000_000_000)
+        );
+    }
+
+    #[test]
+    fn finds_a_cycle() {
+        let jets = sample();
+        let cycle = find_cycle(&jets);
+        assert_eq!(35, cycle.period);
+        assert_eq!(53, cycle.growth);
+        assert_eq!(
+            tower_height_after(&jets, cycle.start) + cycle.growth,
+            tower_height_after(&jets, cycle.start + cycle.period)
+        );
+    }
+
+    #[test]
+    fn same_height_as_simulated() {
+        let jets = sample();
+        for rocks in [0, 1, 10, 100, 2022, 5000] {
+            assert_eq!(tower_height_after(&jets, rocks), tower_height(&jets, rocks));
+        }
+    }
+}
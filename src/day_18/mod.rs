@@ -0,0 +1,102 @@
+mod part_1;
+mod part_2;
+
+use crate::day_18::part_1::surface_area;
+use crate::day_18::part_2::exterior_surface_area;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+use crate::utils::geom::Coord3;
+use std::collections::HashSet;
+
+/// Unit cubes of lava
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Droplet {
+    pub(crate) cubes: HashSet<Coord3>,
+}
+
+impl Droplet {
+    /// Corners of the smallest box holding every cube, `None` when there's no cube
+    pub(crate) fn bounds(&self) -> Option<(Coord3, Coord3)> {
+        let first = *self.cubes.iter().next()?;
+        Some(self.cubes.iter().fold((first, first), |(min, max), cube| {
+            (
+                Coord3::new(min.x.min(cube.x), min.y.min(cube.y), min.z.min(cube.z)),
+                Coord3::new(max.x.max(cube.x), max.y.max(cube.y), max.z.max(cube.z)),
+            )
+        }))
+    }
+}
+
+/// `x,y,z`
+pub(crate) fn parse_cube(line: &str) -> Result<Coord3, String> {
+    let coords = line
+        .split(',')
+        .map(|n| n.trim().parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("Invalid cube {line:?}"))?;
+    match coords.as_slice() {
+        [x, y, z] => Ok(Coord3::new(*x, *y, *z)),
+        _ => Err(format!("Invalid cube {line:?}")),
+    }
+}
+
+pub(crate) struct Day18;
+
+impl Solver for Day18 {
+    const DAY: u8 = 18;
+    type Input = Droplet;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let cubes = parse_lines(input, parse_cube)?;
+        Ok(Droplet {
+            cubes: cubes.into_iter().collect(),
+        })
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(surface_area(input))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(exterior_surface_area(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_18::{parse_cube, Day18, Droplet};
+    use crate::solver::Solver;
+    use crate::utils::geom::Coord3;
+
+    pub(crate) const SAMPLE: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    pub(crate) fn sample() -> Droplet {
+        Day18.parse(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parses_cubes() {
+        assert_eq!(Ok(Coord3::new(1, -2, 3)), parse_cube("1,-2,3"));
+        assert!(parse_cube("1,2").is_err());
+        assert!(parse_cube("1,2,3,4").is_err());
+        assert_eq!(Some(2), Day18.parse("1,1,1\n1,x,1").unwrap_err().line);
+        assert_eq!(
+            Some((Coord3::new(1, 1, 1), Coord3::new(3, 3, 6))),
+            sample().bounds()
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use crate::day_18::Droplet;
+
+/// Faces of the cubes not touching another cube
+pub(crate) fn surface_area(droplet: &Droplet) -> usize {
+    droplet
+        .cubes
+        .iter()
+        .flat_map(|cube| cube.neighbours())
+        .filter(|neighbour| !droplet.cubes.contains(neighbour))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_18::part_1::surface_area;
+    use crate::day_18::tests::sample;
+    use crate::day_18::Droplet;
+    use crate::utils::geom::Coord3;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(64, surface_area(&sample()));
+    }
+
+    #[test]
+    fn two_cubes_side_by_side() {
+        let droplet = Droplet {
+            cubes: [Coord3::new(1, 1, 1), Coord3::new(2, 1, 1)].into(),
+        };
+        assert_eq!(10, surface_area(&droplet));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::day_18::Droplet;
+use crate::utils::geom::Coord3;
+use std::collections::{HashSet, VecDeque};
+
+/// Faces steam can reach: flooding the box around the droplet, one cube wider, from one of its corners
+pub(crate) fn exterior_surface_area(droplet: &Droplet) -> usize {
+    let Some((min, max)) = droplet.bounds() else {
+        return 0;
+    };
+    let (min, max) = (min - Coord3::new(1, 1, 1), max + Coord3::new(1, 1, 1));
+    let inside = |cube: &Coord3| {
+        (min.x..=max.x).contains(&cube.x)
+            && (min.y..=max.y).contains(&cube.y)
+            && (min.z..=max.z).contains(&cube.z)
+    };
+    let mut steam = HashSet::from([min]);
+    let mut queue = VecDeque::from([min]);
+    let mut faces = 0;
+    while let Some(cube) = queue.pop_front() {
+        for neighbour in cube.neighbours().filter(inside) {
+            if droplet.cubes.contains(&neighbour) {
+                faces += 1;
+            } else if steam.insert(neighbour) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    faces
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_18::part_1::surface_area;
+    use crate::day_18::part_2::exterior_surface_area;
+    use crate::day_18::tests::sample;
+    use crate::day_18::Droplet;
+    use crate::utils::geom::Coord3;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(58, exterior_surface_area(&sample()));
+    }
+
+    #[test]
+    fn hollow_cube() {
+        // 3x3x3 with its center missing: the inner faces can't be reached
+        let cubes = (0..27)
+            .map(|i| Coord3::new(i % 3, i / 3 % 3, i / 9))
+            .filter(|cube| *cube != Coord3::new(1, 1, 1))
+            .collect();
+        let droplet = Droplet { cubes };
+        assert_eq!(60, surface_area(&droplet));
+        assert_eq!(54, exterior_surface_area(&droplet));
+        assert_eq!(
+            0,
+            exterior_surface_area(&Droplet {
+                cubes: Default::default()
+            })
+        );
+    }
+}
@@ -0,0 +1,112 @@
+mod parse;
+mod part_1;
+mod part_2;
+mod search;
+
+use crate::day_19::parse::parse_all_blueprints;
+use crate::day_19::part_1::quality_levels;
+use crate::day_19::part_2::first_blueprints_product;
+use crate::solver::{line_of, ParseError, SolveError, Solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Material {
+    Ore = 0,
+    Clay = 1,
+    Obsidian = 2,
+    Geode = 3,
+}
+
+impl Material {
+    pub(crate) const ALL: [Material; 4] = [
+        Material::Ore,
+        Material::Clay,
+        Material::Obsidian,
+        Material::Geode,
+    ];
+}
+
+/// Amounts of each material, indexed by `Material`
+pub(crate) type Amounts = [u32; 4];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Blueprint {
+    pub(crate) id: u32,
+    /// What building a robot collecting each material costs
+    pub(crate) costs: [Amounts; 4],
+}
+
+impl Blueprint {
+    pub(crate) fn cost(&self, robot: Material) -> &Amounts {
+        &self.costs[robot as usize]
+    }
+
+    /// A single robot can be built each minute: more robots than the highest cost in a material are useless
+    pub(crate) fn max_useful_robots(&self) -> Amounts {
+        let mut max = [u32::MAX; 4];
+        for material in [Material::Ore, Material::Clay, Material::Obsidian] {
+            max[material as usize] = self
+                .costs
+                .iter()
+                .map(|cost| cost[material as usize])
+                .max()
+                .unwrap_or(0);
+        }
+        max
+    }
+}
+
+pub(crate) struct Day19;
+
+impl Solver for Day19 {
+    const DAY: u8 = 19;
+    type Input = Vec<Blueprint>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let (rest, blueprints) = parse_all_blueprints(input)
+            .map_err(|e| ParseError::new(format!("Invalid blueprints: {e}")))?;
+        if !rest.trim().is_empty() {
+            return Err(ParseError::at_line(
+                line_of(input, rest),
+                "Invalid blueprint",
+            ));
+        }
+        Ok(blueprints)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u32, SolveError> {
+        Ok(quality_levels(input))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u32, SolveError> {
+        Ok(first_blueprints_product(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_19::{Blueprint, Day19};
+    use crate::solver::Solver;
+
+    pub(crate) const SAMPLE: &str = "\
+Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+    pub(crate) fn sample() -> Vec<Blueprint> {
+        Day19.parse(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn caps_useful_robots() {
+        let blueprints = sample();
+        assert_eq!([4, 14, 7, u32::MAX], blueprints[0].max_useful_robots());
+        assert_eq!([3, 8, 12, u32::MAX], blueprints[1].max_useful_robots());
+    }
+
+    #[test]
+    fn reports_the_line_of_an_invalid_blueprint() {
+        let input = SAMPLE.replace("Blueprint 2:", "Blueprint two:");
+        assert_eq!(Some(2), Day19.parse(&input).unwrap_err().line);
+    }
+}
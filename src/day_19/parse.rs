@@ -0,0 +1,109 @@
+use crate::day_19::{Amounts, Blueprint, Material};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{multispace0, multispace1, u32 as u32p};
+use nom::combinator::{map_res, value};
+use nom::multi::{many1, separated_list0, separated_list1};
+use nom::sequence::{preceded, separated_pair, tuple};
+use nom::IResult;
+
+fn parse_material(input: &str) -> IResult<&str, Material> {
+    alt((
+        value(Material::Ore, tag("ore")),
+        value(Material::Clay, tag("clay")),
+        value(Material::Obsidian, tag("obsidian")),
+        value(Material::Geode, tag("geode")),
+    ))(input)
+}
+
+/// `Each clay robot costs 2 ore.`
+fn parse_robot_cost(input: &str) -> IResult<&str, (Material, Amounts)> {
+    let (rest, (_, robot, _, amounts, _)) = tuple((
+        tag("Each "),
+        parse_material,
+        tag(" robot costs "),
+        separated_list1(tag(" and "), separated_pair(u32p, tag(" "), parse_material)),
+        tag("."),
+    ))(input)?;
+    let mut cost = [0; 4];
+    for (amount, material) in amounts {
+        cost[material as usize] += amount;
+    }
+    Ok((rest, (robot, cost)))
+}
+
+/// Robot costs may be listed in any order, on one line or several, but each robot must be listed once
+pub(crate) fn parse_blueprint(input: &str) -> IResult<&str, Blueprint> {
+    map_res(
+        tuple((
+            preceded(tag("Blueprint "), u32p),
+            tag(":"),
+            many1(preceded(multispace1, parse_robot_cost)),
+        )),
+        |(id, _, robots)| {
+            let mut costs: [Option<Amounts>; 4] = [None; 4];
+            for (robot, cost) in robots {
+                if costs[robot as usize].replace(cost).is_some() {
+                    return Err(format!("Blueprint {id} lists {robot:?} robots twice"));
+                }
+            }
+            let mut all = [[0; 4]; 4];
+            for material in Material::ALL {
+                all[material as usize] = costs[material as usize]
+                    .ok_or(format!("Blueprint {id} misses {material:?} robots"))?;
+            }
+            Ok(Blueprint { id, costs: all })
+        },
+    )(input)
+}
+
+pub(crate) fn parse_all_blueprints(input: &str) -> IResult<&str, Vec<Blueprint>> {
+    preceded(multispace0, separated_list0(multispace1, parse_blueprint))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_19::parse::{parse_all_blueprints, parse_blueprint};
+    use crate::day_19::tests::SAMPLE;
+    use crate::day_19::Material;
+
+    #[test]
+    fn can_parse_blueprint() {
+        let (rest, blueprint) = parse_blueprint(SAMPLE).unwrap();
+        assert!(rest.starts_with('\n'));
+        assert_eq!(1, blueprint.id);
+        assert_eq!(&[4, 0, 0, 0], blueprint.cost(Material::Ore));
+        assert_eq!(&[3, 14, 0, 0], blueprint.cost(Material::Obsidian));
+        assert_eq!(&[2, 0, 7, 0], blueprint.cost(Material::Geode));
+    }
+
+    #[test]
+    fn can_parse_wrapped_blueprints() {
+        let wrapped = "\
+Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+        let (rest, blueprints) = parse_all_blueprints(wrapped).unwrap();
+        assert_eq!("\n", rest);
+        assert_eq!(parse_all_blueprints(SAMPLE).unwrap().1, blueprints);
+    }
+
+    #[test]
+    fn rejects_incomplete_blueprints() {
+        assert!(parse_blueprint("Blueprint 3: Each ore robot costs 4 ore.").is_err());
+        assert!(parse_blueprint(
+            "Blueprint 3: Each ore robot costs 4 ore. Each ore robot costs 2 ore. \
+            Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian."
+        )
+        .is_err());
+    }
+}
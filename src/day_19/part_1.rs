@@ -0,0 +1,22 @@
+use crate::day_19::search::evaluate;
+use crate::day_19::Blueprint;
+
+/// Sum of each blueprint id times the geodes it opens in 24 minutes
+pub(crate) fn quality_levels(blueprints: &[Blueprint]) -> u32 {
+    blueprints
+        .iter()
+        .zip(evaluate(blueprints, 24))
+        .map(|(blueprint, outcome)| blueprint.id * outcome.geodes)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_19::part_1::quality_levels;
+    use crate::day_19::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(33, quality_levels(&sample()));
+    }
+}
@@ -0,0 +1,26 @@
+use crate::day_19::search::evaluate;
+use crate::day_19::Blueprint;
+
+/// Product of the geodes the first 3 blueprints open in 32 minutes, elephants ate the other ones
+pub(crate) fn first_blueprints_product(blueprints: &[Blueprint]) -> u32 {
+    let first = &blueprints[..blueprints.len().min(3)];
+    evaluate(first, 32)
+        .iter()
+        .map(|outcome| outcome.geodes)
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_19::part_2::first_blueprints_product;
+    use crate::day_19::search::max_geodes;
+    use crate::day_19::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        let blueprints = sample();
+        assert_eq!(56, max_geodes(&blueprints[0], 32).geodes);
+        assert_eq!(62, max_geodes(&blueprints[1], 32).geodes);
+        assert_eq!(56 * 62, first_blueprints_product(&blueprints));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::day_19::{Amounts, Blueprint, Material};
+use std::ops::AddAssign;
+use std::thread;
+
+/// How much of the search space was looked at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct SearchStats {
+    /// States explored
+    pub(crate) nodes: u64,
+    /// Branches cut, either because no better score could be reached or because the robot would be useless
+    pub(crate) prunes: u64,
+}
+
+impl AddAssign for SearchStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.nodes += rhs.nodes;
+        self.prunes += rhs.prunes;
+    }
+}
+
+/// Most geodes a blueprint can open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Outcome {
+    pub(crate) geodes: u32,
+    pub(crate) stats: SearchStats,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    minutes_left: u32,
+    robots: Amounts,
+    stock: Amounts,
+}
+
+struct Search<'a> {
+    blueprint: &'a Blueprint,
+    max_robots: Amounts,
+    best: u32,
+    stats: SearchStats,
+}
+
+impl Search<'_> {
+    /// Minutes before `robot` is built, `None` if nothing collects what it needs yet
+    fn time_to_build(&self, state: &State, robot: Material) -> Option<u32> {
+        let mut wait = 0;
+        for material in Material::ALL {
+            let (cost, stock, robots) = (
+                self.blueprint.cost(robot)[material as usize],
+                state.stock[material as usize],
+                state.robots[material as usize],
+            );
+            if cost > stock {
+                if robots == 0 {
+                    return None;
+                }
+                wait = wait.max((cost - stock).div_ceil(robots));
+            }
+        }
+        Some(wait + 1)
+    }
+
+    /// Rather than deciding what to do every minute, decides which robot to build next and waits for it
+    fn explore(&mut self, state: State) {
+        self.stats.nodes += 1;
+        let geode = Material::Geode as usize;
+        let t = state.minutes_left;
+        let idle = state.stock[geode] + state.robots[geode] * t;
+        self.best = self.best.max(idle);
+        // as if a geode robot was built every minute from now on
+        if idle + t * t.saturating_sub(1) / 2 <= self.best {
+            self.stats.prunes += 1;
+            return;
+        }
+        for robot in Material::ALL.into_iter().rev() {
+            if state.robots[robot as usize] >= self.max_robots[robot as usize] {
+                self.stats.prunes += 1;
+                continue;
+            }
+            let Some(minutes) = self.time_to_build(&state, robot) else {
+                continue;
+            };
+            // a robot built during the last minute collects nothing
+            if minutes >= t {
+                continue;
+            }
+            let mut next = state;
+            next.minutes_left -= minutes;
+            for material in Material::ALL {
+                let i = material as usize;
+                next.stock[i] += state.robots[i] * minutes;
+                next.stock[i] -= self.blueprint.cost(robot)[i];
+            }
+            next.robots[robot as usize] += 1;
+            self.explore(next);
+        }
+    }
+}
+
+/// Depth-first, cutting branches that can't beat the best score so far
+pub(crate) fn max_geodes(blueprint: &Blueprint, minutes: u32) -> Outcome {
+    let mut search = Search {
+        blueprint,
+        max_robots: blueprint.max_useful_robots(),
+        best: 0,
+        stats: SearchStats::default(),
+    };
+    let mut robots = [0; 4];
+    robots[Material::Ore as usize] = 1;
+    search.explore(State {
+        minutes_left: minutes,
+        robots,
+        stock: [0; 4],
+    });
+    Outcome {
+        geodes: search.best,
+        stats: search.stats,
+    }
+}
+
+/// Searches every blueprint, spreading them over as many threads as there are cores
+pub(crate) fn evaluate(blueprints: &[Blueprint], minutes: u32) -> Vec<Outcome> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = blueprints.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let handles = blueprints
+            .chunks(chunk)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|blueprint| max_geodes(blueprint, minutes))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Blueprint search panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_19::search::{evaluate, max_geodes, SearchStats};
+    use crate::day_19::tests::sample;
+
+    #[test]
+    fn finds_max_geodes() {
+        let blueprints = sample();
+        let outcome = max_geodes(&blueprints[0], 24);
+        assert_eq!(9, outcome.geodes);
+        assert!(outcome.stats.nodes > 0);
+        assert!(outcome.stats.prunes > 0);
+        assert_eq!(12, max_geodes(&blueprints[1], 24).geodes);
+        assert_eq!(0, max_geodes(&blueprints[0], 0).geodes);
+    }
+
+    #[test]
+    fn same_outcomes_in_parallel() {
+        let blueprints = sample();
+        let outcomes = evaluate(&blueprints, 24);
+        let sequential = blueprints
+            .iter()
+            .map(|blueprint| max_geodes(blueprint, 24))
+            .collect::<Vec<_>>();
+        assert_eq!(sequential, outcomes);
+        let mut total = SearchStats::default();
+        for outcome in &outcomes {
+            total += outcome.stats;
+        }
+        assert!(total.nodes > outcomes[0].stats.nodes);
+        assert!(evaluate(&[], 24).is_empty());
+    }
+}
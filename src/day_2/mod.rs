@@ -1,6 +1,8 @@
 mod part_1;
 mod part_2;
 
+use crate::day_2::part_2::from_line_p2;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
 use std::cmp::Ordering;
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -89,6 +91,47 @@ impl Round {
     }
 }
 
+/// Each line of the strategy guide is understood differently in part 1 and part 2
+pub(crate) struct StrategyGuide {
+    pub(crate) rounds_p1: Vec<Round>,
+    pub(crate) rounds_p2: Vec<Round>,
+}
+
+fn total_score(rounds: &[Round]) -> u64 {
+    rounds.iter().map(|round| round.score() as u64).sum()
+}
+
+pub(crate) struct Day2;
+
+impl Solver for Day2 {
+    const DAY: u8 = 2;
+    type Input = StrategyGuide;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let rounds = parse_lines(input, |line| {
+            Ok::<_, String>((
+                Round::try_from(line.to_string())?,
+                from_line_p2(line.to_string())?,
+            ))
+        })?;
+        let (rounds_p1, rounds_p2) = rounds.into_iter().unzip();
+        Ok(StrategyGuide {
+            rounds_p1,
+            rounds_p2,
+        })
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(total_score(&input.rounds_p1))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(total_score(&input.rounds_p2))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_2::Move;
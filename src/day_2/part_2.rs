@@ -45,7 +45,7 @@ fn from_desired_outcome(opp_move: &Move, outcome: Outcome) -> Move {
     }
 }
 
-fn from_line_p2(line: String) -> Result<Round, String> {
+pub(crate) fn from_line_p2(line: String) -> Result<Round, String> {
     let mut chars = line.chars();
     let c = chars.next().ok_or("Unexpected empty line")?;
     let opponent_move = from_char(c)?;
@@ -0,0 +1,100 @@
+mod part_1;
+mod part_2;
+mod ring;
+
+use crate::day_20::part_1::decrypt;
+use crate::day_20::part_2::decrypt_with_key;
+use crate::day_20::ring::Ring;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+use std::str::FromStr;
+
+/// Moves each number, in the original order, by its own value, `rounds` times
+pub(crate) fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let mut ring = Ring::new(numbers.len());
+    for _ in 0..rounds {
+        for (index, number) in numbers.iter().enumerate() {
+            ring.shift(index, *number);
+        }
+    }
+    ring.order().map(|index| numbers[index]).collect()
+}
+
+/// Sum of the 1000th, 2000th and 3000th numbers after `0`, `None` if there's no `0`
+pub(crate) fn grove_coordinates(mixed: &[i64]) -> Option<i64> {
+    let zero = mixed.iter().position(|n| *n == 0)?;
+    Some(
+        [1000, 2000, 3000]
+            .iter()
+            .map(|offset| mixed[(zero + offset) % mixed.len()])
+            .sum(),
+    )
+}
+
+pub(crate) struct Day20;
+
+impl Solver for Day20 {
+    const DAY: u8 = 20;
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| i64::from_str(line.trim()))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        decrypt(input).ok_or_else(|| SolveError::NoSolution("There's no 0".to_string()))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        decrypt_with_key(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_20::{grove_coordinates, mix};
+
+    pub(crate) const SAMPLE: [i64; 7] = [1, 2, -3, 3, -2, 0, 4];
+
+    /// Straightforward but quadratic: the reference `mix` is checked against
+    pub(crate) fn naive_mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+        let mut mixed = numbers.iter().copied().enumerate().collect::<Vec<_>>();
+        if mixed.len() < 2 {
+            return numbers.to_vec();
+        }
+        for _ in 0..rounds {
+            for index in 0..numbers.len() {
+                let from = mixed.iter().position(|(i, _)| *i == index).unwrap();
+                let entry = mixed.remove(from);
+                let turn = mixed.len() as i64;
+                let to = (from as i64 + entry.1 % turn).rem_euclid(turn);
+                mixed.insert(to as usize, entry);
+            }
+        }
+        mixed.into_iter().map(|(_, n)| n).collect()
+    }
+
+    #[test]
+    fn mixes_sample() {
+        // the same circle as the puzzle's 1, 2, -3, 4, 0, 3, -2
+        assert_eq!(vec![-2, 1, 2, -3, 4, 0, 3], mix(&SAMPLE, 1));
+        assert_eq!(Some(3), grove_coordinates(&mix(&SAMPLE, 1)));
+        assert_eq!(None, grove_coordinates(&[1, 2]));
+    }
+
+    #[quickcheck]
+    fn mixes_as_naive(numbers: Vec<i64>, rounds: u8) -> bool {
+        let rounds = rounds as usize % 4;
+        mix(&numbers, rounds) == naive_mix(&numbers, rounds)
+    }
+
+    #[quickcheck]
+    fn mixing_keeps_numbers(numbers: Vec<i64>) -> bool {
+        let mut mixed = mix(&numbers, 1);
+        let mut numbers = numbers;
+        mixed.sort_unstable();
+        numbers.sort_unstable();
+        mixed == numbers
+    }
+}
@@ -0,0 +1,24 @@
+use crate::day_20::{grove_coordinates, mix};
+
+pub(crate) fn decrypt(numbers: &[i64]) -> Option<i64> {
+    grove_coordinates(&mix(numbers, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_20::grove_coordinates;
+    use crate::day_20::part_1::decrypt;
+    use crate::day_20::tests::{naive_mix, SAMPLE};
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(Some(3), decrypt(&SAMPLE));
+    }
+
+    #[quickcheck]
+    fn decrypts_as_naive(numbers: Vec<i32>, zero_at: usize) -> bool {
+        let mut numbers = numbers.into_iter().map(i64::from).collect::<Vec<_>>();
+        numbers.insert(zero_at % (numbers.len() + 1), 0);
+        decrypt(&numbers) == grove_coordinates(&naive_mix(&numbers, 1))
+    }
+}
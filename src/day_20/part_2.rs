@@ -0,0 +1,47 @@
+use crate::day_20::{grove_coordinates, mix};
+use crate::solver::SolveError;
+
+pub(crate) const DECRYPTION_KEY: i64 = 811_589_153;
+
+/// Numbers times the decryption key, mixed 10 times
+pub(crate) fn decrypt_with_key(numbers: &[i64]) -> Result<i64, SolveError> {
+    let numbers = numbers
+        .iter()
+        .map(|n| {
+            n.checked_mul(DECRYPTION_KEY).ok_or_else(|| {
+                SolveError::NoSolution(format!("{n} overflows once multiplied by the key"))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    grove_coordinates(&mix(&numbers, 10))
+        .ok_or_else(|| SolveError::NoSolution("There's no 0".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_20::grove_coordinates;
+    use crate::day_20::part_2::{decrypt_with_key, DECRYPTION_KEY};
+    use crate::day_20::tests::{naive_mix, SAMPLE};
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(Ok(1_623_178_306), decrypt_with_key(&SAMPLE));
+    }
+
+    #[test]
+    fn rejects_overflowing_keys() {
+        assert!(decrypt_with_key(&[0, i64::MAX / 1000]).is_err());
+        assert!(decrypt_with_key(&[0, i64::MIN / 1000]).is_err());
+    }
+
+    #[quickcheck]
+    fn decrypts_as_naive(numbers: Vec<i32>, zero_at: usize) -> bool {
+        let mut numbers = numbers.into_iter().map(i64::from).collect::<Vec<_>>();
+        numbers.insert(zero_at % (numbers.len() + 1), 0);
+        let keyed = numbers
+            .iter()
+            .map(|n| n * DECRYPTION_KEY)
+            .collect::<Vec<_>>();
+        decrypt_with_key(&numbers).ok() == grove_coordinates(&naive_mix(&keyed, 10))
+    }
+}
@@ -0,0 +1,118 @@
+/// Indices `0..len` laid out in a circle, in blocks of about `sqrt(len)` each:
+/// finding, removing then inserting an index costs `O(sqrt(len))` rather than `O(len)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Ring {
+    blocks: Vec<Vec<usize>>,
+    /// Block holding each index
+    block_of: Vec<usize>,
+    block_size: usize,
+}
+
+impl Ring {
+    /// `0..len`, in order
+    pub(crate) fn new(len: usize) -> Self {
+        let mut ring = Ring {
+            blocks: vec![(0..len).collect()],
+            block_of: vec![0; len],
+            block_size: (len as f64).sqrt().ceil().max(1.0) as usize,
+        };
+        ring.rebalance();
+        ring
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.block_of.len()
+    }
+
+    /// Splits everything again in blocks of `block_size`
+    fn rebalance(&mut self) {
+        let order = self.order().collect::<Vec<_>>();
+        self.blocks = order
+            .chunks(self.block_size)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+        for (b, block) in self.blocks.iter().enumerate() {
+            for index in block {
+                self.block_of[*index] = b;
+            }
+        }
+    }
+
+    /// Where `index` currently is, counting from the start of the first block
+    pub(crate) fn position(&self, index: usize) -> usize {
+        let b = self.block_of[index];
+        let before = self.blocks[..b].iter().map(Vec::len).sum::<usize>();
+        before + self.blocks[b].iter().position(|i| *i == index).unwrap()
+    }
+
+    /// Takes `index` out, returning where it was
+    fn remove(&mut self, index: usize) -> usize {
+        let position = self.position(index);
+        let block = &mut self.blocks[self.block_of[index]];
+        let offset = block.iter().position(|i| *i == index).unwrap();
+        block.remove(offset);
+        position
+    }
+
+    fn insert(&mut self, position: usize, index: usize) {
+        let mut offset = position;
+        let mut b = 0;
+        while b + 1 < self.blocks.len() && offset > self.blocks[b].len() {
+            offset -= self.blocks[b].len();
+            b += 1;
+        }
+        self.blocks[b].insert(offset, index);
+        self.block_of[index] = b;
+        if self.blocks[b].len() > 2 * self.block_size {
+            self.rebalance();
+        }
+    }
+
+    /// Moves `index` forward by `steps` (backwards when negative), the others closing the gap
+    pub(crate) fn shift(&mut self, index: usize, steps: i64) {
+        if self.len() < 2 {
+            return;
+        }
+        let from = self.remove(index);
+        let turn = self.len() as i64 - 1;
+        let to = (from as i64 + steps.rem_euclid(turn)) % turn;
+        self.insert(to as usize, index);
+    }
+
+    /// Indices from the start of the first block
+    pub(crate) fn order(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter().flatten().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_20::ring::Ring;
+
+    #[test]
+    fn shifts_indices() {
+        let mut ring = Ring::new(5);
+        assert_eq!(vec![0, 1, 2, 3, 4], ring.order().collect::<Vec<_>>());
+        ring.shift(1, 2);
+        assert_eq!(vec![0, 2, 3, 1, 4], ring.order().collect::<Vec<_>>());
+        assert_eq!(3, ring.position(1));
+        ring.shift(0, -1);
+        assert_eq!(vec![2, 3, 1, 0, 4], ring.order().collect::<Vec<_>>());
+        // a whole turn is len - 1 steps, the moving index not counting: back at the same place in the circle
+        ring.shift(4, 4 * 3);
+        assert_eq!(vec![4, 2, 3, 1, 0], ring.order().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stays_balanced() {
+        let mut ring = Ring::new(100);
+        for _ in 0..50 {
+            ring.shift(7, 1);
+        }
+        assert_eq!(57, ring.position(7));
+        assert!(ring.blocks.iter().all(|block| block.len() <= 20));
+        let mut order = ring.order().collect::<Vec<_>>();
+        order.sort_unstable();
+        assert_eq!((0..100).collect::<Vec<_>>(), order);
+    }
+}
@@ -0,0 +1,249 @@
+mod parse;
+mod part_1;
+mod part_2;
+
+use crate::day_21::parse::parse_all_monkeys_def;
+use crate::day_21::part_1::root_number;
+use crate::day_21::part_2::human_number;
+use crate::solver::{line_of, ParseError, SolveError, Solver};
+use std::collections::HashMap;
+
+pub(crate) const ROOT: &str = "root";
+pub(crate) const HUMAN: &str = "humn";
+
+/// The operations of `day_11::Op`, plus subtraction and division
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Job {
+    Yell(i64),
+    Math { lhs: String, op: Op, rhs: String },
+}
+
+/// A job, the monkeys it waits for being referred to by their index in the `Riddle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Node {
+    Number(i64),
+    Math(usize, Op, usize),
+}
+
+/// `None` on overflow, or when dividing by 0
+pub(crate) fn apply(op: Op, lhs: i64, rhs: i64) -> Option<i64> {
+    match op {
+        Op::Add => lhs.checked_add(rhs),
+        Op::Sub => lhs.checked_sub(rhs),
+        Op::Mul => lhs.checked_mul(rhs),
+        Op::Div => lhs.checked_div(rhs),
+    }
+}
+
+/// Monkeys as the nodes of an expression DAG
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Riddle {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    nodes: Vec<Node>,
+    /// Every monkey after the ones it waits for
+    order: Vec<usize>,
+}
+
+impl Riddle {
+    /// Fails when a monkey waits for an unknown monkey, or ends up waiting for itself
+    pub(crate) fn new(monkeys: Vec<(String, Job)>) -> Result<Self, String> {
+        let mut index = HashMap::new();
+        for (i, (name, _)) in monkeys.iter().enumerate() {
+            if index.insert(name.clone(), i).is_some() {
+                return Err(format!("Monkey {name} has two jobs"));
+            }
+        }
+        let id = |name: &String| {
+            index
+                .get(name)
+                .copied()
+                .ok_or(format!("Unknown monkey {name}"))
+        };
+        let nodes = monkeys
+            .iter()
+            .map(|(_, job)| match job {
+                Job::Yell(n) => Ok(Node::Number(*n)),
+                Job::Math { lhs, op, rhs } => Ok(Node::Math(id(lhs)?, *op, id(rhs)?)),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let names = monkeys.into_iter().map(|(name, _)| name).collect();
+        let mut riddle = Riddle {
+            names,
+            index,
+            nodes,
+            order: vec![],
+        };
+        riddle.order = riddle.topological_order()?;
+        Ok(riddle)
+    }
+
+    /// Depth-first, each monkey once all the ones it waits for are done
+    fn topological_order(&self) -> Result<Vec<usize>, String> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Visiting,
+            Done,
+        }
+        let mut marks = vec![Mark::New; self.nodes.len()];
+        let mut order = Vec::with_capacity(self.nodes.len());
+        for start in 0..self.nodes.len() {
+            let mut stack = vec![(start, false)];
+            while let Some((node, expanded)) = stack.pop() {
+                if expanded {
+                    marks[node] = Mark::Done;
+                    order.push(node);
+                    continue;
+                }
+                match marks[node] {
+                    Mark::Done => continue,
+                    Mark::Visiting => {
+                        return Err(format!("Monkey {} waits for itself", self.names[node]))
+                    }
+                    Mark::New => {}
+                }
+                marks[node] = Mark::Visiting;
+                stack.push((node, true));
+                if let Node::Math(lhs, _, rhs) = self.nodes[node] {
+                    for next in [rhs, lhs] {
+                        if marks[next] != Mark::Done {
+                            stack.push((next, false));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    pub(crate) fn id(&self, name: &str) -> Result<usize, String> {
+        self.index
+            .get(name)
+            .copied()
+            .ok_or(format!("There's no monkey {name}"))
+    }
+
+    pub(crate) fn node(&self, id: usize) -> Node {
+        self.nodes[id]
+    }
+
+    pub(crate) fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// What every monkey yells, `overridden` replacing the job of a monkey.
+    /// `None` for the monkeys that depend on an unknown number
+    pub(crate) fn evaluate(
+        &self,
+        overridden: Option<(usize, Option<i64>)>,
+    ) -> Result<Vec<Option<i64>>, String> {
+        let mut values = vec![None; self.nodes.len()];
+        for id in &self.order {
+            values[*id] = match (overridden, self.nodes[*id]) {
+                (Some((overridden, value)), _) if overridden == *id => value,
+                (_, Node::Number(n)) => Some(n),
+                (_, Node::Math(lhs, op, rhs)) => match (values[lhs], values[rhs]) {
+                    (Some(lhs), Some(rhs)) => Some(apply(op, lhs, rhs).ok_or(format!(
+                        "Monkey {} can't compute {lhs} {op:?} {rhs}",
+                        self.names[*id]
+                    ))?),
+                    _ => None,
+                },
+            };
+        }
+        Ok(values)
+    }
+}
+
+pub(crate) struct Day21;
+
+impl Solver for Day21 {
+    const DAY: u8 = 21;
+    type Input = Riddle;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let (rest, monkeys) = parse_all_monkeys_def(input)
+            .map_err(|e| ParseError::new(format!("Invalid monkey jobs: {e}")))?;
+        if !rest.trim().is_empty() {
+            return Err(ParseError::at_line(
+                line_of(input, rest),
+                "Invalid monkey job",
+            ));
+        }
+        Riddle::new(monkeys).map_err(ParseError::new)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        root_number(input).map_err(SolveError::NoSolution)
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        human_number(input).map_err(SolveError::NoSolution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_21::{Day21, Riddle};
+    use crate::solver::Solver;
+
+    pub(crate) const SAMPLE: &str = "\
+root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+    pub(crate) fn sample() -> Riddle {
+        Day21.parse(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn orders_monkeys_after_their_operands() {
+        let riddle = sample();
+        assert_eq!(15, riddle.order.len());
+        let rank = |name: &str| {
+            let id = riddle.id(name).unwrap();
+            riddle.order.iter().position(|i| *i == id).unwrap()
+        };
+        assert!(rank("humn") < rank("ptdq"));
+        assert!(rank("pppw") < rank("root"));
+        assert!(rank("sjmn") < rank("root"));
+    }
+
+    #[test]
+    fn rejects_broken_riddles() {
+        assert!(Day21.parse("root: abcd + efgh\nabcd: 1").is_err());
+        assert!(Day21
+            .parse("root: abcd + efgh\nabcd: efgh * efgh\nefgh: root - abcd")
+            .is_err());
+        assert!(Day21.parse("root: 1\nroot: 2").is_err());
+    }
+
+    #[test]
+    fn reports_the_line_of_an_invalid_job() {
+        let input = SAMPLE.replace("ptdq: humn - dvpt", "ptdq: humn % dvpt");
+        assert_eq!(Some(5), Day21.parse(&input).unwrap_err().line);
+    }
+}
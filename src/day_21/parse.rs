@@ -0,0 +1,82 @@
+use crate::day_21::{Job, Op};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha1, i64 as i64p, line_ending};
+use nom::combinator::{map, value};
+use nom::multi::separated_list0;
+use nom::sequence::{separated_pair, tuple};
+use nom::IResult;
+
+fn parse_name(def: &str) -> IResult<&str, String> {
+    map(alpha1, str::to_string)(def)
+}
+
+/// Surrounded by spaces, as in ` * `
+fn parse_op(def: &str) -> IResult<&str, Op> {
+    alt((
+        value(Op::Add, tag(" + ")),
+        value(Op::Sub, tag(" - ")),
+        value(Op::Mul, tag(" * ")),
+        value(Op::Div, tag(" / ")),
+    ))(def)
+}
+
+fn parse_job(def: &str) -> IResult<&str, Job> {
+    let parse_yell = map(i64p, Job::Yell);
+    let parse_math = map(
+        tuple((parse_name, parse_op, parse_name)),
+        |(lhs, op, rhs)| Job::Math { lhs, op, rhs },
+    );
+    alt((parse_yell, parse_math))(def)
+}
+
+/// `root: pppw + sjmn`
+pub(crate) fn parse_monkey_def(def: &str) -> IResult<&str, (String, Job)> {
+    separated_pair(parse_name, tag(": "), parse_job)(def)
+}
+
+pub(crate) fn parse_all_monkeys_def(input: &str) -> IResult<&str, Vec<(String, Job)>> {
+    separated_list0(line_ending, parse_monkey_def)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_21::parse::{parse_all_monkeys_def, parse_job, parse_monkey_def};
+    use crate::day_21::tests::SAMPLE;
+    use crate::day_21::{Job, Op};
+
+    #[test]
+    fn can_parse_monkey() {
+        let (_, monkey) = parse_monkey_def("root: pppw + sjmn").unwrap();
+        assert_eq!(
+            (
+                "root".to_string(),
+                Job::Math {
+                    lhs: "pppw".to_string(),
+                    op: Op::Add,
+                    rhs: "sjmn".to_string()
+                }
+            ),
+            monkey
+        );
+        let (_, monkey) = parse_monkey_def("dbpl: 5").unwrap();
+        assert_eq!(("dbpl".to_string(), Job::Yell(5)), monkey);
+        assert!(parse_monkey_def("root: pppw ^ sjmn").is_err());
+        let ops = ["a - b", "a / b"]
+            .iter()
+            .map(|def| parse_job(def).unwrap().1)
+            .map(|job| match job {
+                Job::Math { op, .. } => op,
+                Job::Yell(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Op::Sub, Op::Div], ops);
+    }
+
+    #[test]
+    fn can_parse_sample() {
+        let (rest, monkeys) = parse_all_monkeys_def(SAMPLE).unwrap();
+        assert_eq!("", rest);
+        assert_eq!(15, monkeys.len());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::day_21::{Riddle, ROOT};
+
+pub(crate) fn root_number(riddle: &Riddle) -> Result<i64, String> {
+    let root = riddle.id(ROOT)?;
+    riddle.evaluate(None)?[root].ok_or(format!("{ROOT} yells nothing"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_21::part_1::root_number;
+    use crate::day_21::tests::sample;
+    use crate::day_21::Day21;
+    use crate::solver::Solver;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(Ok(152), root_number(&sample()));
+    }
+
+    #[test]
+    fn fails_on_division_by_zero() {
+        let riddle = Day21.parse("root: abcd / efgh\nabcd: 1\nefgh: 0").unwrap();
+        assert!(root_number(&riddle).is_err());
+    }
+}
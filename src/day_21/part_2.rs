@@ -0,0 +1,98 @@
+use crate::day_21::{Node, Op, Riddle, HUMAN, ROOT};
+
+/// `x` such that `x op known == target`
+fn solve_lhs(op: Op, known: i64, target: i64) -> Option<i64> {
+    match op {
+        Op::Add => target.checked_sub(known),
+        Op::Sub => target.checked_add(known),
+        Op::Mul => (known != 0 && target % known == 0).then(|| target / known),
+        Op::Div => target.checked_mul(known),
+    }
+}
+
+/// `x` such that `known op x == target`
+fn solve_rhs(op: Op, known: i64, target: i64) -> Option<i64> {
+    match op {
+        Op::Add => target.checked_sub(known),
+        Op::Sub => known.checked_sub(target),
+        Op::Mul => (known != 0 && target % known == 0).then(|| target / known),
+        Op::Div => (target != 0).then(|| known / target),
+    }
+}
+
+/// What to yell for both monkeys `root` waits for to yell the same number.
+/// Works its way down from `root`, inverting each operation, so `humn` must appear only once
+pub(crate) fn human_number(riddle: &Riddle) -> Result<i64, String> {
+    let (root, human) = (riddle.id(ROOT)?, riddle.id(HUMAN)?);
+    let Node::Math(lhs, _, rhs) = riddle.node(root) else {
+        return Err(format!("{ROOT} doesn't wait for anyone"));
+    };
+    let values = riddle.evaluate(Some((human, None)))?;
+    let (mut unknown, mut target) = match (values[lhs], values[rhs]) {
+        (None, Some(target)) => (lhs, target),
+        (Some(target), None) => (rhs, target),
+        (Some(_), Some(_)) => return Err(format!("{ROOT} doesn't depend on {HUMAN}")),
+        (None, None) => return Err(format!("{HUMAN} is on both sides of {ROOT}")),
+    };
+    while unknown != human {
+        let Node::Math(lhs, op, rhs) = riddle.node(unknown) else {
+            unreachable!("Only {HUMAN} and the monkeys waiting for it are unknown")
+        };
+        let solved = match (values[lhs], values[rhs]) {
+            (None, Some(known)) => {
+                unknown = lhs;
+                solve_lhs(op, known, target)
+            }
+            (Some(known), None) => {
+                unknown = rhs;
+                solve_rhs(op, known, target)
+            }
+            _ => {
+                return Err(format!(
+                    "{HUMAN} is on both sides of {}",
+                    riddle.name(unknown)
+                ))
+            }
+        };
+        target = solved.ok_or(format!("{} can't yell {target}", riddle.name(unknown)))?;
+    }
+    // integer divisions may not give it back
+    let values = riddle.evaluate(Some((human, Some(target))))?;
+    if values[lhs] != values[rhs] {
+        return Err(format!("No integer for {HUMAN} to yell"));
+    }
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_21::part_2::human_number;
+    use crate::day_21::tests::sample;
+    use crate::day_21::Day21;
+    use crate::solver::Solver;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(Ok(301), human_number(&sample()));
+    }
+
+    #[test]
+    fn inverts_every_op() {
+        // (20 - humn) / 2 == 8
+        let riddle = Day21
+            .parse("root: abcd + efgh\nabcd: ijkl / mnop\nijkl: qrst - humn\nqrst: 20\nmnop: 2\nefgh: 8\nhumn: 0")
+            .unwrap();
+        assert_eq!(Ok(4), human_number(&riddle));
+        // humn * 3 == 10
+        let riddle = Day21
+            .parse("root: abcd + efgh\nabcd: humn * mnop\nmnop: 3\nefgh: 10\nhumn: 0")
+            .unwrap();
+        assert!(human_number(&riddle).is_err());
+    }
+
+    #[test]
+    fn needs_human_once() {
+        let riddle = Day21.parse("root: humn + humn\nhumn: 1").unwrap();
+        assert!(human_number(&riddle).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::day_22::{Board, Tile, Wrap};
+use crate::utils::geom::Direction::{Down, Left, Right, Up};
+use crate::utils::geom::{Coord, Coord3, Direction, DIRECTIONS};
+use std::collections::{HashMap, VecDeque};
+
+/// Where a square of the net ends up once folded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Face {
+    /// Top-left tile of the face on the map
+    corner: Coord,
+    /// Where the `x` and `y` axes of the map point to on this face
+    right: Coord3,
+    down: Coord3,
+    /// Pointing out of the cube
+    normal: Coord3,
+}
+
+impl Face {
+    fn towards(&self, direction: Direction) -> Coord3 {
+        match direction {
+            Right => self.right,
+            Left => -self.right,
+            Down => self.down,
+            Up => -self.down,
+        }
+    }
+
+    /// The face next to this one towards `direction` on the map, folded along their shared edge
+    fn fold(&self, direction: Direction, size: i64) -> Face {
+        let corner = self.corner + direction.delta() * size;
+        let (right, down, normal) = match direction {
+            Right => (-self.normal, self.down, self.right),
+            Left => (self.normal, self.down, -self.right),
+            Down => (self.right, -self.normal, self.down),
+            Up => (self.right, self.normal, -self.down),
+        };
+        Face {
+            corner,
+            right,
+            down,
+            normal,
+        }
+    }
+
+    /// Center of `tile` on a cube centered on the origin, its sides being `2 * size` long to stay on integers
+    fn point(&self, tile: Coord, size: i64) -> Coord3 {
+        let local = tile - self.corner;
+        self.normal * size
+            + self.right * (2 * local.x + 1 - size)
+            + self.down * (2 * local.y + 1 - size)
+    }
+}
+
+/// Off an edge of the map, onto the face sharing this edge once the map is folded into a cube
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CubeWrap {
+    /// Where walking off the map from a tile, towards a direction, leads to
+    edges: HashMap<(Coord, Direction), (Coord, Direction)>,
+}
+
+impl CubeWrap {
+    /// Folds the map, whichever net of the cube it is, starting from its first face
+    pub(crate) fn new(board: &Board) -> Result<Self, String> {
+        let tiles = board.size();
+        let size = (tiles as f64 / 6.0).sqrt().round() as i64;
+        if size == 0 || (6 * size * size) as usize != tiles {
+            return Err(format!("{tiles} tiles can't fold into a cube"));
+        }
+        let corner_of = |tile: Coord| Coord::new(tile.x / size * size, tile.y / size * size);
+        let first = Face {
+            corner: corner_of(board.start()),
+            right: Coord3::new(1, 0, 0),
+            down: Coord3::new(0, 1, 0),
+            normal: Coord3::new(0, 0, 1),
+        };
+        let mut faces = HashMap::from([(first.corner, first)]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            for direction in DIRECTIONS {
+                let next = face.fold(direction, size);
+                if board.get(&next.corner) != Tile::Void && !faces.contains_key(&next.corner) {
+                    faces.insert(next.corner, next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut points = HashMap::new();
+        for tile in board.tiles() {
+            let face = faces
+                .get(&corner_of(tile))
+                .ok_or(format!("{tile:?} isn't on a face of the cube"))?;
+            if points
+                .insert(face.point(tile, size), (tile, *face))
+                .is_some()
+            {
+                return Err("Faces of the cube overlap".to_string());
+            }
+        }
+        let mut edges = HashMap::new();
+        for (tile, face) in points.values() {
+            for direction in DIRECTIONS {
+                if board.get(&(*tile + direction.delta())) != Tile::Void {
+                    continue;
+                }
+                // over the edge, then down the next face
+                let point = face.point(*tile, size) + face.towards(direction) - face.normal;
+                let (next, next_face) = points[&point];
+                let facing = DIRECTIONS
+                    .into_iter()
+                    .find(|d| next_face.towards(*d) == -face.normal)
+                    .unwrap();
+                edges.insert((*tile, direction), (next, facing));
+            }
+        }
+        Ok(CubeWrap { edges })
+    }
+}
+
+impl Wrap for CubeWrap {
+    fn wrap(&self, _: &Board, from: Coord, facing: Direction) -> (Coord, Direction) {
+        self.edges[&(from, facing)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_22::cube::CubeWrap;
+    use crate::day_22::tests::sample;
+    use crate::day_22::{Board, Wrap};
+    use crate::utils::geom::Direction::{Down, Left, Right, Up};
+    use crate::utils::geom::{Coord, DIRECTIONS};
+
+    /// Open tiles only, each `#` of `layout` being a face
+    fn net(layout: &str, size: usize) -> Board {
+        let map = layout
+            .lines()
+            .flat_map(|line| {
+                let row = line
+                    .chars()
+                    .map(|ch| if ch == '#' { "." } else { " " }.repeat(size))
+                    .collect::<String>();
+                std::iter::repeat_n(row, size)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Board::new(&map).unwrap()
+    }
+
+    fn goes_around(board: &Board) {
+        let wrap = CubeWrap::new(board).unwrap();
+        let size = (board.size() / 6).isqrt();
+        for tile in board.tiles() {
+            for direction in DIRECTIONS {
+                let mut current = (tile, direction);
+                for _ in 0..4 * size {
+                    current = board.ahead(current.0, current.1, &wrap);
+                }
+                assert_eq!((tile, direction), current);
+            }
+        }
+    }
+
+    #[test]
+    fn folds_the_sample() {
+        let board = sample().board;
+        let wrap = CubeWrap::new(&board).unwrap();
+        // the examples of the puzzle
+        assert_eq!(
+            (Coord::new(14, 8), Down),
+            wrap.wrap(&board, Coord::new(11, 5), Right)
+        );
+        assert_eq!(
+            (Coord::new(1, 7), Up),
+            wrap.wrap(&board, Coord::new(10, 11), Down)
+        );
+        assert_eq!(
+            (Coord::new(8, 1), Right),
+            wrap.wrap(&board, Coord::new(5, 4), Up)
+        );
+        assert_eq!(
+            (Coord::new(5, 4), Down),
+            wrap.wrap(&board, Coord::new(8, 1), Left)
+        );
+        goes_around(&board);
+    }
+
+    #[test]
+    fn folds_any_net() {
+        for layout in [".##\n.#.\n##.\n#..", "#...\n####\n#...", "##..\n.###\n..#."] {
+            for size in [1, 3] {
+                goes_around(&net(layout, size));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_what_does_not_fold() {
+        assert!(CubeWrap::new(&net("###\n###", 2)).is_err());
+        assert!(CubeWrap::new(&net("####", 2)).is_err());
+        assert!(CubeWrap::new(&net("#####\n#....", 1)).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+mod cube;
+mod part_1;
+mod part_2;
+
+use crate::day_22::part_1::password_on_flat_map;
+use crate::day_22::part_2::password_on_cube;
+use crate::solver::{ParseError, SolveError, Solver};
+use crate::utils::geom::{Coord, Direction};
+use crate::utils::grid::Grid;
+use nom::branch::alt;
+use nom::character::complete::{char, u32 as u32p};
+use nom::combinator::{all_consuming, map, value};
+use nom::multi::many1;
+use nom::IResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Tile {
+    /// Off the map
+    Void,
+    Open,
+    Wall,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            ' ' => Ok(Tile::Void),
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            _ => Err(format!("Unknown tile {value:?}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Step {
+    Forward(u32),
+    TurnLeft,
+    TurnRight,
+}
+
+/// Where one ends up after walking off the edge of the map
+pub(crate) trait Wrap {
+    /// `from` being the last tile on the map, walking towards `facing`
+    fn wrap(&self, board: &Board, from: Coord, facing: Direction) -> (Coord, Direction);
+}
+
+/// The map, padded with `Tile::Void` into a rectangle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Board {
+    tiles: Grid<Tile>,
+}
+
+impl Board {
+    pub(crate) fn new(map: &str) -> Result<Self, ParseError> {
+        let width = map.lines().map(|line| line.chars().count()).max();
+        let rows = map
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let mut row = line
+                    .chars()
+                    .map(Tile::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| ParseError::at_line(i + 1, e))?;
+                row.resize(width.unwrap_or(0), Tile::Void);
+                Ok(row)
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        let tiles = Grid::from_rows(rows).map_err(ParseError::new)?;
+        if tiles.position(|tile| *tile == Tile::Open).is_none() {
+            return Err(ParseError::new("There's no open tile"));
+        }
+        Ok(Board { tiles })
+    }
+
+    /// `Tile::Void` out of the map as well
+    pub(crate) fn get(&self, coord: &Coord) -> Tile {
+        self.tiles.get(coord).copied().unwrap_or(Tile::Void)
+    }
+
+    /// Coordinates of every tile on the map
+    pub(crate) fn tiles(&self) -> impl Iterator<Item = Coord> + '_ {
+        self.tiles
+            .iter()
+            .filter(|(_, tile)| **tile != Tile::Void)
+            .map(|(coord, _)| coord)
+    }
+
+    /// Leftmost open tile of the top row
+    pub(crate) fn start(&self) -> Coord {
+        self.tiles
+            .position(|tile| *tile == Tile::Open)
+            .expect("There's no open tile")
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|(_, tile)| **tile != Tile::Void)
+            .count()
+    }
+
+    /// The tile in front of `position`, and which way one faces there
+    pub(crate) fn ahead(
+        &self,
+        position: Coord,
+        facing: Direction,
+        wrap: &impl Wrap,
+    ) -> (Coord, Direction) {
+        let next = position + facing.delta();
+        if self.get(&next) == Tile::Void {
+            wrap.wrap(self, position, facing)
+        } else {
+            (next, facing)
+        }
+    }
+
+    /// Follows `path` from the start, facing right, stopping in front of walls
+    pub(crate) fn walk(&self, path: &[Step], wrap: &impl Wrap) -> (Coord, Direction) {
+        let mut position = self.start();
+        let mut facing = Direction::Right;
+        for step in path {
+            match step {
+                Step::TurnLeft => facing = facing.turn_left(),
+                Step::TurnRight => facing = facing.turn_right(),
+                Step::Forward(n) => {
+                    for _ in 0..*n {
+                        let next = self.ahead(position, facing, wrap);
+                        if self.get(&next.0) == Tile::Wall {
+                            break;
+                        }
+                        (position, facing) = next;
+                    }
+                }
+            }
+        }
+        (position, facing)
+    }
+}
+
+pub(crate) fn password(position: Coord, facing: Direction) -> i64 {
+    1000 * (position.y + 1) + 4 * (position.x + 1) + facing.facing() as i64
+}
+
+/// `10R5L5`
+fn parse_path(input: &str) -> IResult<&str, Vec<Step>> {
+    all_consuming(many1(alt((
+        map(u32p, Step::Forward),
+        value(Step::TurnLeft, char('L')),
+        value(Step::TurnRight, char('R')),
+    ))))(input)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Notes {
+    pub(crate) board: Board,
+    pub(crate) path: Vec<Step>,
+}
+
+pub(crate) struct Day22;
+
+impl Solver for Day22 {
+    const DAY: u8 = 22;
+    type Input = Notes;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let input = input.replace("\r\n", "\n");
+        let (map, path) = input.split_once("\n\n").ok_or(ParseError::new(
+            "Expecting the map, a blank line, then the path",
+        ))?;
+        let board = Board::new(map)?;
+        let (_, path) = parse_path(path.trim()).map_err(|_| {
+            ParseError::at_line(map.lines().count() + 2, format!("Invalid path {path:?}"))
+        })?;
+        Ok(Notes { board, path })
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        Ok(password_on_flat_map(input))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<i64, SolveError> {
+        password_on_cube(input).map_err(SolveError::NoSolution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_22::{Day22, Notes, Step, Tile};
+    use crate::solver::Solver;
+    use crate::utils::geom::Coord;
+
+    pub(crate) const SAMPLE: &str = concat!(
+        "        ...#\n",
+        "        .#..\n",
+        "        #...\n",
+        "        ....\n",
+        "...#.......#\n",
+        "........#...\n",
+        "..#....#....\n",
+        "..........#.\n",
+        "        ...#....\n",
+        "        .....#..\n",
+        "        .#......\n",
+        "        ......#.\n",
+        "\n",
+        "10R5L5R10L4R5L5\n",
+    );
+
+    pub(crate) fn sample() -> Notes {
+        Day22.parse(SAMPLE).unwrap()
+    }
+
+    #[test]
+    fn parses_notes() {
+        let notes = sample();
+        assert_eq!(Coord::new(8, 0), notes.board.start());
+        assert_eq!(Tile::Wall, notes.board.get(&Coord::new(11, 0)));
+        assert_eq!(Tile::Void, notes.board.get(&Coord::new(12, 0)));
+        assert_eq!(Tile::Void, notes.board.get(&Coord::new(-1, 0)));
+        assert_eq!(96, notes.board.size());
+        assert_eq!(
+            vec![Step::Forward(10), Step::TurnRight, Step::Forward(5)],
+            notes.path[..3]
+        );
+        assert!(Day22.parse("..\n..\n\n10X").is_err());
+        assert!(Day22.parse("..\n.x\n\n10R").is_err());
+        assert!(Day22.parse("..\n..\n10R").is_err());
+        assert!(Day22.parse("##\n##\n\n10R").is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::day_22::{password, Board, Notes, Tile, Wrap};
+use crate::utils::geom::{Coord, Direction};
+
+/// Off one edge of the map, back on the opposite one, on the same row or column
+pub(crate) struct FlatWrap;
+
+impl Wrap for FlatWrap {
+    fn wrap(&self, board: &Board, from: Coord, facing: Direction) -> (Coord, Direction) {
+        let back = facing.opposite().delta();
+        let mut position = from;
+        while board.get(&(position + back)) != Tile::Void {
+            position += back;
+        }
+        (position, facing)
+    }
+}
+
+pub(crate) fn password_on_flat_map(notes: &Notes) -> i64 {
+    let (position, facing) = notes.board.walk(&notes.path, &FlatWrap);
+    password(position, facing)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_22::part_1::{password_on_flat_map, FlatWrap};
+    use crate::day_22::tests::sample;
+    use crate::day_22::Wrap;
+    use crate::utils::geom::{Coord, Direction};
+
+    #[test]
+    fn check_sample() {
+        let notes = sample();
+        assert_eq!(
+            (Coord::new(7, 5), Direction::Right),
+            notes.board.walk(&notes.path, &FlatWrap)
+        );
+        assert_eq!(6032, password_on_flat_map(&notes));
+    }
+
+    #[test]
+    fn wraps_on_the_same_line() {
+        let board = sample().board;
+        assert_eq!(
+            (Coord::new(0, 6), Direction::Right),
+            FlatWrap.wrap(&board, Coord::new(11, 6), Direction::Right)
+        );
+        assert_eq!(
+            (Coord::new(5, 7), Direction::Up),
+            FlatWrap.wrap(&board, Coord::new(5, 4), Direction::Up)
+        );
+    }
+}
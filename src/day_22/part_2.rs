@@ -0,0 +1,19 @@
+use crate::day_22::cube::CubeWrap;
+use crate::day_22::{password, Notes};
+
+pub(crate) fn password_on_cube(notes: &Notes) -> Result<i64, String> {
+    let wrap = CubeWrap::new(&notes.board)?;
+    let (position, facing) = notes.board.walk(&notes.path, &wrap);
+    Ok(password(position, facing))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_22::part_2::password_on_cube;
+    use crate::day_22::tests::sample;
+
+    #[test]
+    fn check_sample() {
+        assert_eq!(Ok(5031), password_on_cube(&sample()));
+    }
+}
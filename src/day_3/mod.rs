@@ -1,6 +1,10 @@
 mod part_1;
 mod part_2;
 
+use crate::day_3::part_1::duplicated_priority;
+use crate::day_3::part_2::badge_sum;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+
 pub(crate) fn priority(item: char) -> u8 {
     if item.is_ascii_lowercase() {
         (item as u8) - b'a' + 1
@@ -11,6 +15,44 @@ pub(crate) fn priority(item: char) -> u8 {
     }
 }
 
+pub(crate) struct Day3;
+
+impl Solver for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let rucksacks = parse_lines(input, |line| {
+            if !line.chars().all(|c| c.is_ascii_alphabetic()) {
+                Err(format!("Non ascii letter in rucksack {line:?}"))
+            } else if !line.len().is_multiple_of(2) {
+                Err(format!(
+                    "Rucksack {line:?} can't be split in two compartments"
+                ))
+            } else {
+                Ok(line.to_string())
+            }
+        })?;
+        if !rucksacks.len().is_multiple_of(3) {
+            return Err(ParseError::new("Expecting groups of 3 elves"));
+        }
+        Ok(rucksacks)
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(input
+            .iter()
+            .map(|line| duplicated_priority(line.clone()) as u64)
+            .sum())
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(badge_sum(input, String::clone))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_3::priority;
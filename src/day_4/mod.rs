@@ -1,8 +1,14 @@
 mod part_1;
 mod part_2;
 
+use crate::day_4::part_1::count_contained;
+use crate::day_4::part_2::count_overlaps;
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
+use crate::utils::ranges::contains;
+use std::convert::identity;
 use std::ops::RangeInclusive;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct RangePair {
     fst: RangeInclusive<u32>,
     snd: RangeInclusive<u32>,
@@ -11,23 +17,56 @@ pub(crate) struct RangePair {
 impl RangePair {
     /// Returns true if one range is fully contained in the other
     pub(crate) fn fully_contained(&self) -> bool {
-        (self.fst.contains(self.snd.start()) && self.fst.contains(self.snd.end()))
-            || self.snd.contains(self.fst.start()) && self.snd.contains(self.fst.end())
+        contains(&self.fst, &self.snd) || contains(&self.snd, &self.fst)
+    }
+}
+
+impl TryFrom<&str> for RangePair {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (fst, snd) = value
+            .split_once(',')
+            .ok_or(format!("Expecting two comma-separated ranges in {value:?}"))?;
+        Ok(RangePair {
+            fst: parse_range(fst)?,
+            snd: parse_range(snd)?,
+        })
     }
 }
 
 pub(crate) fn parse_ranges(line: String) -> RangePair {
-    let mut blocks = line.split(',');
-    let (fst, snd) = (blocks.next().unwrap(), blocks.next().unwrap());
-    let (fst, snd) = (parse_range(fst), parse_range(snd));
-    RangePair { fst, snd }
+    RangePair::try_from(line.as_str()).unwrap()
 }
-fn parse_range(str: &str) -> RangeInclusive<u32> {
-    let mut blocks = str.split('-');
-    let (start, end) = (blocks.next().unwrap(), blocks.next().unwrap());
-    let start = start.parse::<u32>().unwrap();
-    let end = end.parse::<u32>().unwrap();
-    start..=end
+
+fn parse_range(str: &str) -> Result<RangeInclusive<u32>, String> {
+    let (start, end) = str
+        .split_once('-')
+        .ok_or(format!("Invalid range {str:?}"))?;
+    let start = start.parse::<u32>().map_err(|e| e.to_string())?;
+    let end = end.parse::<u32>().map_err(|e| e.to_string())?;
+    Ok(start..=end)
+}
+
+pub(crate) struct Day4;
+
+impl Solver for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<RangePair>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| RangePair::try_from(line))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(count_contained(input.iter().cloned(), identity))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(count_overlaps(input.iter().cloned(), identity))
+    }
 }
 
 #[cfg(test)]
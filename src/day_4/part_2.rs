@@ -1,10 +1,8 @@
 use crate::day_4::RangePair;
+use crate::utils::ranges;
 
 fn overlap(range: &RangePair) -> bool {
-    range.fst.contains(range.snd.start())
-        || range.fst.contains(range.snd.end())
-        || range.snd.contains(range.fst.start())
-        || range.snd.contains(range.fst.end())
+    ranges::overlap(&range.fst, &range.snd)
 }
 
 pub(crate) fn count_overlaps<A, T: IntoIterator<Item = A>>(
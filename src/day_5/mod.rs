@@ -1,6 +1,9 @@
 mod part_1;
 mod part_2;
 
+use crate::day_5::part_1::CratesV1;
+use crate::day_5::part_2::CratesV2;
+use crate::solver::{ParseError, SolveError, Solver};
 use regex::Regex;
 use std::collections::VecDeque;
 
@@ -59,6 +62,60 @@ fn parse<C: Crates, L, I: IntoIterator<Item = L>>(file: I, mapping: fn(L) -> Str
     crates
 }
 
+/// `parse` panics on malformed input, so we're checking the drawing and moves beforehand
+fn validate(input: &str) -> Result<(), ParseError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let separator = lines
+        .iter()
+        .position(|line| line.is_empty())
+        .ok_or(ParseError::new("Missing blank line after the drawing"))?;
+    if separator == 0 {
+        return Err(ParseError::at_line(1, "Missing drawing"));
+    }
+    let stack_count = lines[separator - 1]
+        .split_whitespace()
+        .last()
+        .and_then(|count| count.parse::<usize>().ok())
+        .ok_or(ParseError::at_line(separator, "Expecting stack numbers"))?;
+    let line_regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    for (i, line) in lines.iter().enumerate().skip(separator + 1) {
+        let capts = line_regex
+            .captures(line)
+            .ok_or(ParseError::at_line(i + 1, format!("Invalid move {line:?}")))?;
+        for stack in [&capts[2], &capts[3]] {
+            match stack.parse::<usize>() {
+                Ok(stack) if (1..=stack_count).contains(&stack) => {}
+                _ => return Err(ParseError::at_line(i + 1, format!("Unknown stack {stack}"))),
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) struct Day5;
+
+impl Solver for Day5 {
+    const DAY: u8 = 5;
+    type Input = Vec<String>;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        validate(input)?;
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<String, SolveError> {
+        let crates: CratesV1 = parse(input, String::clone);
+        Ok(crates.collect_crates())
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<String, SolveError> {
+        let crates: CratesV2 = parse(input, String::clone);
+        Ok(crates.collect_crates())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils::io::{input_file_lines, FileLines};
@@ -2,7 +2,7 @@ use crate::day_5::Crates;
 use std::collections::VecDeque;
 
 #[derive(Debug, PartialEq, Eq)]
-struct CratesV1 {
+pub(crate) struct CratesV1 {
     stacks: Vec<VecDeque<char>>,
 }
 
@@ -2,7 +2,7 @@ use crate::day_5::Crates;
 use std::collections::VecDeque;
 
 #[derive(Debug, PartialEq, Eq)]
-struct CratesV2 {
+pub(crate) struct CratesV2 {
     stacks: Vec<VecDeque<char>>,
 }
 
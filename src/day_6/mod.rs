@@ -1,3 +1,4 @@
+use crate::solver::{ParseError, SolveError, Solver};
 use std::collections::{HashSet, VecDeque};
 
 mod part_1;
@@ -60,6 +61,41 @@ fn first_marker(input: String, size: usize) -> usize {
     usize::MAX
 }
 
+pub(crate) struct Day6;
+
+impl Solver for Day6 {
+    const DAY: u8 = 6;
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .next()
+            .map(str::to_string)
+            .ok_or(ParseError::new("Empty datastream"))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        marker_found(first_marker(input.clone(), 4))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        marker_found(first_marker(input.clone(), 14))
+    }
+}
+
+fn marker_found(marker: usize) -> Result<usize, SolveError> {
+    if marker == usize::MAX {
+        Err(SolveError::NoSolution(
+            "no marker in datastream".to_string(),
+        ))
+    } else {
+        Ok(marker)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils::io::{input_file_lines, FileLines};
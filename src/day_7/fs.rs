@@ -0,0 +1,155 @@
+use crate::day_7::FileDesc;
+use id_tree::{NodeId, Tree};
+use std::collections::HashMap;
+
+/// The tree built from a terminal session, every directory size computed once and for all
+pub(crate) struct FileSystem {
+    tree: Tree<FileDesc>,
+    /// Directories only, files carry their own size
+    sizes: HashMap<NodeId, u64>,
+}
+
+impl FileSystem {
+    pub(crate) fn new(tree: Tree<FileDesc>) -> Self {
+        let mut sizes = HashMap::new();
+        if let Some(root) = tree.root_node_id() {
+            // children come first: their sizes are known by the time their parent is reached
+            for id in tree.traverse_post_order_ids(root).unwrap() {
+                let node = tree.get(&id).unwrap();
+                if let FileDesc::Dir(_) = node.data() {
+                    let size = node
+                        .children()
+                        .iter()
+                        .map(|child| match tree.get(child).unwrap().data() {
+                            FileDesc::File(size, _) => *size,
+                            FileDesc::Dir(_) => sizes[child],
+                        })
+                        .sum();
+                    sizes.insert(id, size);
+                }
+            }
+        }
+        FileSystem { tree, sizes }
+    }
+
+    fn size_of(&self, id: &NodeId) -> u64 {
+        match self.tree.get(id).unwrap().data() {
+            FileDesc::File(size, _) => *size,
+            FileDesc::Dir(_) => self.sizes[id],
+        }
+    }
+
+    fn path_of(&self, id: &NodeId) -> String {
+        let mut names = self
+            .tree
+            .ancestors(id)
+            .unwrap()
+            .map(|node| node.data().name())
+            .collect::<Vec<_>>();
+        // the root itself is named `/`
+        names.pop();
+        names.reverse();
+        names.push(self.tree.get(id).unwrap().data().name());
+        match names.as_slice() {
+            ["/"] => "/".to_string(),
+            names => format!("/{}", names.join("/")),
+        }
+    }
+
+    /// Size of the whole disk in use
+    pub(crate) fn used(&self) -> u64 {
+        self.tree
+            .root_node_id()
+            .map_or(0, |root| self.size_of(root))
+    }
+
+    /// Every directory with its size, each one followed by its sub-directories, as `du` lists them
+    pub(crate) fn du(&self) -> Vec<(String, u64)> {
+        let root = match self.tree.root_node_id() {
+            Some(root) => root,
+            None => return vec![],
+        };
+        self.tree
+            .traverse_pre_order_ids(root)
+            .unwrap()
+            .filter_map(|id| Some((self.path_of(&id), *self.sizes.get(&id)?)))
+            .collect()
+    }
+
+    /// Directories, with their size, whose size matches `predicate`
+    pub(crate) fn find(&self, predicate: impl Fn(u64) -> bool) -> Vec<(String, u64)> {
+        self.du()
+            .into_iter()
+            .filter(|(_, size)| predicate(*size))
+            .collect()
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl FileSystem {
+    fn lookup_id(&self, path: &str) -> Option<NodeId> {
+        let mut current = self.tree.root_node_id()?.clone();
+        for part in path.split('/').filter(|part| !part.is_empty()) {
+            current = self
+                .tree
+                .children_ids(&current)
+                .unwrap()
+                .find(|child| self.tree.get(child).unwrap().data().name() == part)?
+                .clone();
+        }
+        Some(current)
+    }
+
+    /// The file or directory at an absolute `path`, such as `/a/e`
+    pub(crate) fn lookup(&self, path: &str) -> Option<&FileDesc> {
+        self.lookup_id(path)
+            .map(|id| self.tree.get(&id).unwrap().data())
+    }
+
+    /// Size of a file, or of everything below a directory
+    pub(crate) fn size(&self, path: &str) -> Option<u64> {
+        self.lookup_id(path).map(|id| self.size_of(&id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_7::build_tree;
+    use crate::day_7::fs::FileSystem;
+    use crate::day_7::tests::SAMPLE;
+    use crate::day_7::FileDesc;
+
+    fn sample() -> FileSystem {
+        FileSystem::new(build_tree(SAMPLE.lines(), str::to_string).unwrap())
+    }
+
+    #[test]
+    fn looks_paths_up() {
+        let fs = sample();
+        assert_eq!(
+            Some(&FileDesc::File(584, "i".to_string())),
+            fs.lookup("/a/e/i")
+        );
+        assert_eq!(Some(&FileDesc::Dir("d".to_string())), fs.lookup("/d/"));
+        assert_eq!(Some(&FileDesc::Dir("/".to_string())), fs.lookup("/"));
+        assert_eq!(None, fs.lookup("/a/i"));
+        assert_eq!(Some(94853), fs.size("/a"));
+        assert_eq!(Some(2557), fs.size("/a/g"));
+        assert_eq!(48381165, fs.used());
+    }
+
+    #[test]
+    fn lists_like_du() {
+        let expected = vec![
+            ("/".to_string(), 48381165),
+            ("/a".to_string(), 94853),
+            ("/a/e".to_string(), 584),
+            ("/d".to_string(), 24933642),
+        ];
+        assert_eq!(expected, sample().du());
+        assert_eq!(
+            vec![("/a".to_string(), 94853), ("/a/e".to_string(), 584)],
+            sample().find(|size| size <= 100_000)
+        );
+    }
+}
@@ -1,15 +1,22 @@
+mod fs;
 mod part_1;
 mod part_2;
 
+use crate::day_7::fs::FileSystem;
+use crate::day_7::part_1::total_size_of_at_most;
+use crate::day_7::part_2::{dir_to_delete, DISK_SIZE, NEEDED};
+use crate::solver::{ParseError, SolveError, Solver};
 use id_tree::InsertBehavior;
 use id_tree::Node;
+use id_tree::NodeId;
 use id_tree::Tree;
 use nom::branch::alt;
-use nom::bytes::complete::take_while1;
-use nom::bytes::streaming::tag;
-use nom::combinator::map;
+use nom::bytes::complete::tag;
+use nom::combinator::{all_consuming, map, rest, verify};
 use nom::sequence::separated_pair;
 use nom::IResult;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum FileDesc {
@@ -47,15 +54,13 @@ enum Line {
     Output(FileDesc),
 }
 
+/// Whatever follows, spaces and all
 fn parse_entry_name(line: &str) -> IResult<&str, String> {
-    map(
-        take_while1(|c: char| "abcdefghijklmnopqrstuvwxyz./".contains(c)),
-        str::to_string,
-    )(line)
+    map(verify(rest, |name: &str| !name.is_empty()), str::to_string)(line)
 }
 
 fn parse_ls(line: &str) -> IResult<&str, Cmd> {
-    map(tag("ls"), |_| Cmd::Ls)(line)
+    map(all_consuming(tag("ls")), |_| Cmd::Ls)(line)
 }
 
 fn parse_cd(line: &str) -> IResult<&str, Cmd> {
@@ -91,10 +96,92 @@ fn parse_line(line: &str) -> IResult<&str, Line> {
     alt((line_cmd_parser, line_output_parser))(line)
 }
 
+impl FileDesc {
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            FileDesc::Dir(name) | FileDesc::File(_, name) => name,
+        }
+    }
+}
+
+/// What can go wrong replaying a terminal session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SessionError {
+    /// A command other than `cd` and `ls`, or missing its argument
+    UnknownCommand(String),
+    /// Neither a command nor a listing entry
+    Unrecognized(String),
+    /// A listing entry that no `ls` printed
+    UnexpectedOutput,
+    /// `cd ..` from `/`
+    AboveRoot,
+    NotADirectory(String),
+    /// An entry listed again, but not as it was the first time
+    Conflict(String),
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::UnknownCommand(cmd) => write!(f, "Unknown command {cmd:?}"),
+            SessionError::Unrecognized(line) => write!(f, "Unrecognized terminal line {line:?}"),
+            SessionError::UnexpectedOutput => write!(f, "Listing entry out of any ls output"),
+            SessionError::AboveRoot => write!(f, "Cannot cd above /"),
+            SessionError::NotADirectory(name) => write!(f, "{name:?} is not a directory"),
+            SessionError::Conflict(name) => {
+                write!(f, "{name:?} does not match what was listed before")
+            }
+        }
+    }
+}
+
+/// A `SessionError` along with the (1-based) line it happened at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TerminalError {
+    pub(crate) line: usize,
+    pub(crate) error: SessionError,
+}
+
+impl From<TerminalError> for ParseError {
+    fn from(value: TerminalError) -> Self {
+        ParseError::at_line(value.line, value.error.to_string())
+    }
+}
+
+fn read_line(line: &str) -> Result<Line, SessionError> {
+    match all_consuming(parse_line)(line) {
+        Ok((_, parsed)) => Ok(parsed),
+        Err(_) if line.starts_with("$ ") => Err(SessionError::UnknownCommand(line.to_string())),
+        Err(_) => Err(SessionError::Unrecognized(line.to_string())),
+    }
+}
+
+/// Adds `desc` under `parent` unless already there, returning its id
+fn insert_entry(
+    tree: &mut Tree<FileDesc>,
+    parent: &NodeId,
+    desc: FileDesc,
+) -> Result<NodeId, SessionError> {
+    let existing = tree
+        .children_ids(parent)
+        .unwrap()
+        .find(|id| tree.get(id).unwrap().data().name() == desc.name())
+        .cloned();
+    match existing {
+        Some(id) if tree.get(&id).unwrap().data() == &desc => Ok(id),
+        Some(_) => Err(SessionError::Conflict(desc.name().to_string())),
+        None => Ok(tree
+            .insert(Node::new(desc), InsertBehavior::UnderNode(parent))
+            .unwrap()),
+    }
+}
+
+/// Replays a terminal session. Lines are trimmed at the end, directories visited or listed again
+/// are not duplicated, and `cd` into a directory no `ls` showed yet creates it
 pub(crate) fn build_tree<A, I: IntoIterator<Item = A>>(
     lines: I,
     mapping: fn(A) -> String,
-) -> Tree<FileDesc> {
+) -> Result<Tree<FileDesc>, TerminalError> {
     let mut tree = Tree::<FileDesc>::new();
     let root = tree
         .insert(
@@ -102,54 +189,71 @@ pub(crate) fn build_tree<A, I: IntoIterator<Item = A>>(
             InsertBehavior::AsRoot,
         )
         .unwrap();
-    let mut curr_dir = root;
-    for line in lines {
+    let mut curr_dir = root.clone();
+    let mut listing = false;
+    for (i, line) in lines.into_iter().enumerate() {
         let line = mapping(line);
-        let (_, parsed) = parse_line(line.as_str()).unwrap();
-        match parsed {
-            Line::Input(Cmd::Ls) => { /* see output parsing */ }
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+        let at_line = |error| TerminalError { line: i + 1, error };
+        match read_line(line).map_err(at_line)? {
+            Line::Input(Cmd::Ls) => listing = true,
             Line::Input(Cmd::Cd(dir)) => {
-                if ".." == dir {
-                    curr_dir = tree.get(&curr_dir).unwrap().parent().unwrap().clone();
-                } else if "/" == dir {
-                    // Only used at start
-                    // curr_dir = root.clone;
-                } else {
-                    let node = Node::new(FileDesc::Dir(dir));
-                    curr_dir = tree
-                        .insert(node, InsertBehavior::UnderNode(&curr_dir))
-                        .unwrap();
-                }
+                listing = false;
+                curr_dir = match dir.as_str() {
+                    "/" => root.clone(),
+                    ".." => tree
+                        .get(&curr_dir)
+                        .unwrap()
+                        .parent()
+                        .ok_or(at_line(SessionError::AboveRoot))?
+                        .clone(),
+                    _ => insert_entry(&mut tree, &curr_dir, FileDesc::Dir(dir.clone()))
+                        .map_err(|e| match e {
+                            SessionError::Conflict(name) => SessionError::NotADirectory(name),
+                            e => e,
+                        })
+                        .map_err(at_line)?,
+                };
             }
+            Line::Output(_) if !listing => return Err(at_line(SessionError::UnexpectedOutput)),
             Line::Output(desc) => {
-                match desc {
-                    FileDesc::Dir(_) => { /* ignore, cf `cd dir ` */ }
-                    FileDesc::File(_, _) => {
-                        let node = Node::new(desc);
-                        tree.insert(node, InsertBehavior::UnderNode(&curr_dir))
-                            .unwrap();
-                    }
-                }
+                insert_entry(&mut tree, &curr_dir, desc).map_err(at_line)?;
             }
         }
     }
-    tree
+    Ok(tree)
 }
 
-pub(crate) fn total_size(tree: &Tree<FileDesc>, curr: &Node<FileDesc>) -> u64 {
-    let mut total = 0;
-    for node_id in curr.children() {
-        let node = tree.get(node_id).unwrap();
-        match node.data() {
-            FileDesc::Dir(_) => total += total_size(tree, node),
-            FileDesc::File(size, _) => total += size,
-        }
+pub(crate) struct Day7;
+
+impl Solver for Day7 {
+    const DAY: u8 = 7;
+    type Input = FileSystem;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let tree = build_tree(input.lines(), str::to_string)?;
+        Ok(FileSystem::new(tree))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(total_size_of_at_most(input, 100_000))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        dir_to_delete(input, DISK_SIZE, NEEDED)
+            .map(|(_, size)| size)
+            .ok_or_else(|| SolveError::NoSolution("Not enough space to free".to_string()))
     }
-    total
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::day_7::fs::FileSystem;
     use crate::day_7::parse_dir_entry;
     use crate::day_7::parse_entry_name;
     use crate::day_7::parse_file_desc;
@@ -157,6 +261,7 @@ mod tests {
     use crate::day_7::Cmd;
     use crate::day_7::FileDesc::Dir;
     use crate::day_7::Line;
+    use crate::day_7::{build_tree, SessionError, TerminalError};
     use crate::utils::io::{input_file_lines, FileLines};
 
     pub(crate) const SAMPLE: &str = "\
@@ -216,4 +321,65 @@ $ ls
     pub(crate) fn sample() -> FileLines {
         input_file_lines("day_7.txt").unwrap()
     }
+
+    fn replay(session: &str) -> Result<FileSystem, TerminalError> {
+        build_tree(session.lines(), str::to_string).map(FileSystem::new)
+    }
+
+    #[test]
+    fn accepts_any_file_name() {
+        let fs = replay("$ cd /\n$ ls\ndir My Documents\n12 notes (v2).TXT\n$ cd My Documents\n$ ls\n7 ~$draft.docx").unwrap();
+        assert_eq!(Some(12), fs.size("/notes (v2).TXT"));
+        assert_eq!(Some(7), fs.size("/My Documents"));
+        assert_eq!(Some(19), fs.size("/"));
+    }
+
+    #[test]
+    fn does_not_duplicate_revisited_dirs() {
+        let session = "\
+$ cd /
+$ ls
+dir a
+10 b
+$ cd a
+$ ls
+20 c
+$ cd /
+$ ls
+dir a
+10 b
+$ cd a
+$ ls
+20 c
+$ cd ..
+$ cd a";
+        let fs = replay(session).unwrap();
+        assert_eq!(30, fs.used());
+        assert_eq!(vec![("/".to_string(), 30), ("/a".to_string(), 20)], fs.du());
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let error = |session: &str| replay(session).err();
+        let at = |line, error| Some(TerminalError { line, error });
+        assert_eq!(at(2, SessionError::AboveRoot), error("$ cd /\n$ cd .."));
+        assert_eq!(
+            at(2, SessionError::UnknownCommand("$ pwd".to_string())),
+            error("$ cd /\n$ pwd")
+        );
+        assert_eq!(
+            at(1, SessionError::Unrecognized("hello".to_string())),
+            error("hello")
+        );
+        assert_eq!(at(2, SessionError::UnexpectedOutput), error("$ cd /\n10 a"));
+        assert_eq!(
+            at(4, SessionError::Conflict("a".to_string())),
+            error("$ ls\n10 a\n$ ls\n11 a")
+        );
+        assert_eq!(
+            at(3, SessionError::NotADirectory("a".to_string())),
+            error("$ ls\n10 a\n$ cd a")
+        );
+        assert!(error("$ ls\n10 a\n$ ls\n10 a\n\n").is_none());
+    }
 }
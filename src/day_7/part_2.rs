@@ -1,40 +1,38 @@
-use crate::day_7::{total_size, FileDesc};
-use id_tree::Tree;
+use crate::day_7::fs::FileSystem;
 
-fn candidates_to_deletion(tree: &Tree<FileDesc>, min_threshold: u64) -> Vec<(&String, u64)> {
-    tree.traverse_pre_order(tree.root_node_id().unwrap())
-        .unwrap()
-        .filter_map(|node| match node.data() {
-            FileDesc::Dir(name) => {
-                let total = total_size(tree, node);
-                if total >= min_threshold {
-                    Some((name, total))
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        })
-        .collect()
+pub(crate) const DISK_SIZE: u64 = 70_000_000;
+pub(crate) const NEEDED: u64 = 30_000_000;
+
+/// The smallest directory to delete for `needed` to be available on a `disk_size` disk, with its size.
+/// `None` when even emptying the whole disk would not be enough
+pub(crate) fn dir_to_delete(fs: &FileSystem, disk_size: u64, needed: u64) -> Option<(String, u64)> {
+    let to_free = needed.saturating_sub(disk_size.saturating_sub(fs.used()));
+    fs.find(|size| size >= to_free)
+        .into_iter()
+        .min_by_key(|(_, size)| *size)
 }
 
 #[cfg(test)]
 mod tests {
-    // use crate::day_7::build_tree;
-    // use crate::day_7::part_2::candidates_to_deletion;
-    // use crate::day_7::tests::{sample, SAMPLE};
+    use crate::day_7::build_tree;
+    use crate::day_7::fs::FileSystem;
+    use crate::day_7::part_2::{dir_to_delete, DISK_SIZE, NEEDED};
+    use crate::day_7::tests::{sample, SAMPLE};
 
-    // #[test]
-    // fn test_sample() {
-    //     let tree = build_tree(SAMPLE.lines(), str::to_string);
-    //     let candidates = candidates_to_deletion(&tree, 30_000_000);
-    //     println!("{candidates:?}")
-    // }
+    #[test]
+    fn test_sample() {
+        let fs = FileSystem::new(build_tree(SAMPLE.lines(), str::to_string).unwrap());
+        assert_eq!(
+            Some(("/d".to_string(), 24933642)),
+            dir_to_delete(&fs, DISK_SIZE, NEEDED)
+        );
+        assert_eq!(None, dir_to_delete(&fs, DISK_SIZE, 80_000_000));
+    }
 
-    // #[test]
-    // fn solution() {
-    //     let tree = build_tree(sample(), Result::unwrap);
-    //     let candidates = candidates_to_deletion(&tree, 30_000_000);
-    //     println!("{candidates:?}")
-    // }
+    #[test]
+    fn solution() {
+        let fs = FileSystem::new(build_tree(sample(), Result::unwrap).unwrap());
+        let (_, size) = dir_to_delete(&fs, DISK_SIZE, NEEDED).unwrap();
+        assert_eq!(549173, size);
+    }
 }
@@ -1,138 +1,70 @@
 mod part_1;
 mod part_2;
 
-use std::collections::HashSet;
+use crate::day_8::part_2::highest_scenic_score;
+use crate::solver::{ParseError, SolveError, Solver};
+use crate::utils::geom::{Coord, DIRECTIONS};
+use crate::utils::grid::Grid;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-pub(crate) struct Tree {
-    x: usize,
-    y: usize,
-    height: u32,
-}
+/// Tree heights
+pub(crate) type Forest = Grid<u32>;
 
-pub(crate) type Forest = Vec<Vec<Tree>>; // squared
+/// A tree is visible when every tree between it and an edge is shorter, in at least one direction
+fn is_visible(forest: &Forest, tree: &Coord) -> bool {
+    let height = forest[tree];
+    DIRECTIONS.iter().any(|direction| {
+        forest
+            .towards(tree, direction)
+            .all(|(_, other)| *other < height)
+    })
+}
 
-fn visible_trees(forest: &Vec<Vec<char>>) -> HashSet<Tree> {
-    let len = forest.len();
-    let mut visible = HashSet::new();
-    for i in 0..len {
-        let mut tallest_from_left = -1;
-        let mut tallest_from_right = -1;
+fn visible_trees(forest: &Forest) -> Vec<Coord> {
+    forest
+        .coords()
+        .filter(|tree| is_visible(forest, tree))
+        .collect()
+}
 
-        let mut tallest_from_top = -1;
-        let mut tallest_from_bottom = -1;
+pub(crate) fn parse_forest(input: &str) -> Result<Forest, ParseError> {
+    Grid::parse(input, |c| {
+        c.to_digit(10)
+            .ok_or(format!("Expecting tree heights, got {c:?}"))
+    })
+}
 
-        let row = forest.get(i).unwrap();
-        let row_len = row.len();
+pub(crate) struct Day8;
 
-        for j in 0..row_len {
-            // Row by Row
-            let h_left = row.get(j).unwrap();
-            let left_tree = Tree {
-                x: i,
-                y: j,
-                height: h_left.to_digit(10).unwrap(),
-            };
-            let h_right = row.get(row_len - j - 1).unwrap();
-            let right_tree = Tree {
-                x: i,
-                y: row_len - j - 1,
-                height: h_right.to_digit(10).unwrap(),
-            };
-            if left_tree.height as i64 > tallest_from_left {
-                tallest_from_left = left_tree.height as i64;
-                visible.insert(left_tree);
-            }
-            if right_tree.height as i64 > tallest_from_right {
-                tallest_from_right = right_tree.height as i64;
-                visible.insert(right_tree);
-            }
+impl Solver for Day8 {
+    const DAY: u8 = 8;
+    type Input = Forest;
+    type Answer1 = usize;
+    type Answer2 = u64;
 
-            // Col by Col
-            let h_top = forest.get(j).unwrap().get(i).unwrap();
-            let top_tree = Tree {
-                x: j,
-                y: i,
-                height: h_top.to_digit(10).unwrap(),
-            };
-            let h_bottom = forest.get(len - j - 1).unwrap().get(i).unwrap();
-            let bottom_tree = Tree {
-                x: len - j - 1,
-                y: i,
-                height: h_bottom.to_digit(10).unwrap(),
-            };
-            if top_tree.height as i64 > tallest_from_top {
-                tallest_from_top = top_tree.height as i64;
-                visible.insert(top_tree);
-            }
-            if bottom_tree.height as i64 > tallest_from_bottom {
-                tallest_from_bottom = bottom_tree.height as i64;
-                visible.insert(bottom_tree);
-            }
-        }
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_forest(input)
     }
-    visible
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::day_8::{visible_trees, Forest, Tree};
-    use crate::utils::io::input_file_lines;
 
-    pub(crate) fn sample() -> Vec<Vec<char>> {
-        vec![
-            vec!['3', '0', '3', '7', '3'],
-            vec!['2', '5', '5', '1', '2'],
-            vec!['6', '5', '3', '3', '2'],
-            vec!['3', '3', '5', '4', '9'],
-            vec!['3', '5', '3', '9', '0'],
-        ]
+    fn part_1(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(visible_trees(input).len())
     }
 
-    fn create_forest(origin: Vec<Vec<char>>) -> Forest {
-        origin
-            .iter()
-            .enumerate()
-            .map(|(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(|(j, c)| Tree {
-                        x: i,
-                        y: j,
-                        height: c.to_digit(10).unwrap(),
-                    })
-                    .collect::<Vec<Tree>>()
-            })
-            .collect()
+    fn part_2(&self, input: &Self::Input) -> Result<u64, SolveError> {
+        Ok(highest_scenic_score(input))
     }
+}
 
-    pub(crate) fn sample_forest() -> Forest {
-        create_forest(sample())
-    }
+#[cfg(test)]
+mod tests {
+    use crate::day_8::{parse_forest, visible_trees, Forest};
+    use crate::utils::io::input_string;
 
-    pub(crate) fn puzzle_input() -> Vec<Vec<char>> {
-        input_file_lines("day_8.txt")
-            .unwrap()
-            .map(|line| line.unwrap().chars().collect::<Vec<char>>())
-            .collect()
+    pub(crate) fn sample() -> Forest {
+        parse_forest("30373\n25512\n65332\n33549\n35390").unwrap()
     }
 
-    pub(crate) fn puzzle_input_forest() -> Forest {
-        input_file_lines("day_8.txt")
-            .unwrap()
-            .enumerate()
-            .map(|(i, line)| {
-                line.unwrap()
-                    .chars()
-                    .enumerate()
-                    .map(|(j, c)| Tree {
-                        x: i,
-                        y: j,
-                        height: c.to_digit(10).unwrap(),
-                    })
-                    .collect()
-            })
-            .collect()
+    pub(crate) fn puzzle_input() -> Forest {
+        parse_forest(&input_string("day_8.txt").unwrap()).unwrap()
     }
 
     #[test]
@@ -140,4 +72,10 @@ mod tests {
         let visible = visible_trees(&sample());
         assert_eq!(21, visible.len());
     }
+
+    #[test]
+    fn rejects_non_digits() {
+        assert!(parse_forest("303\n2x5\n653").is_err());
+        assert!(parse_forest("303\n25\n653").is_err());
+    }
 }
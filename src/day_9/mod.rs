@@ -1,3 +1,4 @@
+use crate::solver::{parse_lines, ParseError, SolveError, Solver};
 use crate::utils::geom::Direction::{Down, Left, Right, Up};
 use crate::utils::geom::{Coord, Direction};
 
@@ -11,36 +12,15 @@ pub(crate) struct Move {
 }
 
 pub(crate) fn new_coords(start: &Coord, direction: &Direction) -> Coord {
-    match direction {
-        Up => Coord {
-            x: start.x,
-            y: start.y - 1,
-        },
-        Down => Coord {
-            x: start.x,
-            y: start.y + 1,
-        },
-        Left => Coord {
-            x: start.x - 1,
-            y: start.y,
-        },
-        Right => Coord {
-            x: start.x + 1,
-            y: start.y,
-        },
-    }
+    *start + direction.delta()
 }
 
+/// Knots stay in touch, diagonals included, moving one step towards the head otherwise
 pub(crate) fn next_pos(origin: &Coord, head_pos: &Coord) -> Coord {
-    let d_x = head_pos.x - origin.x;
-    let d_y = head_pos.y - origin.y;
-    if d_x.abs() > 1 || d_y.abs() > 1 {
-        Coord {
-            x: origin.x + d_x.signum(),
-            y: origin.y + d_y.signum(),
-        }
+    if origin.chebyshev(head_pos) > 1 {
+        *origin + (*head_pos - *origin).signum()
     } else {
-        origin.clone()
+        *origin
     }
 }
 
@@ -76,6 +56,27 @@ impl TryFrom<String> for Move {
     }
 }
 
+pub(crate) struct Day9;
+
+impl Solver for Day9 {
+    const DAY: u8 = 9;
+    type Input = Vec<Move>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| Move::try_from(line.to_string()))
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(part_1::tail_positions(input))
+    }
+
+    fn part_2(&self, input: &Self::Input) -> Result<usize, SolveError> {
+        Ok(part_2::tail_positions(input, 10))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_9::Direction::{Down, Left, Right, Up};
@@ -13,7 +13,7 @@ fn do_move(current: Rope, mov: &Move, traversed: &mut HashSet<Coord>) -> Rope {
     for _ in 0..mov.qty {
         let new_head_pos = new_coords(&state.head, &mov.direction);
         let new_tail_pos = next_pos(&state.tail, &new_head_pos);
-        traversed.insert(new_tail_pos.clone());
+        traversed.insert(new_tail_pos);
         state = Rope {
             head: new_head_pos,
             tail: new_tail_pos,
@@ -22,6 +22,15 @@ fn do_move(current: Rope, mov: &Move, traversed: &mut HashSet<Coord>) -> Rope {
     state
 }
 
+pub(crate) fn tail_positions(moves: &[Move]) -> usize {
+    let mut curr = Rope::default();
+    let mut traversed = HashSet::new();
+    for mov in moves {
+        curr = do_move(curr, mov, &mut traversed);
+    }
+    traversed.len()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_9::part_1::{do_move, Rope};
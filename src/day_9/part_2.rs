@@ -14,7 +14,7 @@ impl Rope {
     }
 
     fn tail_pos(&self) -> Coord {
-        self.positions.back().unwrap().clone()
+        *self.positions.back().unwrap()
     }
 
     fn move_towards(&mut self, direction: &Direction) {
@@ -37,6 +37,15 @@ impl Rope {
     }
 }
 
+pub(crate) fn tail_positions(moves: &[Move], rope_size: usize) -> usize {
+    let mut rope = Rope::new(rope_size);
+    let mut traversed = HashSet::new();
+    for mov in moves {
+        rope.mov(mov, &mut traversed);
+    }
+    traversed.len()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_9::part_2::Rope;
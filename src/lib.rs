@@ -1,8 +1,20 @@
+pub mod bench;
+pub mod client;
 mod day_1;
 mod day_10;
 mod day_11;
 mod day_12;
+mod day_13;
+mod day_14;
+mod day_15;
+mod day_16;
+mod day_17;
+mod day_18;
+mod day_19;
 mod day_2;
+mod day_20;
+mod day_21;
+mod day_22;
 mod day_3;
 mod day_4;
 mod day_5;
@@ -10,8 +22,8 @@ mod day_6;
 mod day_7;
 mod day_8;
 mod day_9;
-pub(crate) mod utils;
-mod day_13;
+pub mod solver;
+pub mod utils;
 
 #[cfg(test)]
 #[macro_use(quickcheck)]
@@ -0,0 +1,349 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::bench::{measure, Measured};
+use crate::{
+    day_1, day_10, day_11, day_12, day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_2,
+    day_20, day_21, day_22, day_3, day_4, day_5, day_6, day_7, day_8, day_9,
+};
+
+/// Every puzzle comes in two halves, the second one being unlocked once the first is solved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("Unknown part {value}, expecting 1 or 2")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// An input that could not be understood, `line` is 1-based when we know where it went wrong
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        ParseError {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn at_line(line: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    Parse(ParseError),
+    /// This half of the puzzle has no implementation (yet)
+    Unsolved,
+    /// The input was well-formed but does not lead to an answer
+    NoSolution(String),
+}
+
+impl From<ParseError> for SolveError {
+    fn from(value: ParseError) -> Self {
+        SolveError::Parse(value)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse(err) => write!(f, "invalid input, {err}"),
+            SolveError::Unsolved => write!(f, "not solved yet"),
+            SolveError::NoSolution(reason) => write!(f, "no solution: {reason}"),
+        }
+    }
+}
+
+/// A day of the calendar: parsing the input once, then answering both parts from it
+pub trait Solver {
+    const DAY: u8;
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(&self, input: &Self::Input) -> Result<Self::Answer1, SolveError>;
+    fn part_2(&self, input: &Self::Input) -> Result<Self::Answer2, SolveError>;
+}
+
+/// Object-safe view over a `Solver`, so that days with different input / answer types can live side by side
+pub trait Puzzle {
+    fn day(&self) -> u8;
+    fn solve(&self, part: Part, input: &str) -> Result<String, SolveError>;
+    /// Same as `solve`, measuring parsing and solving apart
+    fn solve_measured(&self, part: Part, input: &str) -> Result<Measured, SolveError>;
+}
+
+impl<S: Solver> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn solve(&self, part: Part, input: &str) -> Result<String, SolveError> {
+        let parsed = self.parse(input)?;
+        match part {
+            Part::One => self.part_1(&parsed).map(|answer| answer.to_string()),
+            Part::Two => self.part_2(&parsed).map(|answer| answer.to_string()),
+        }
+    }
+
+    fn solve_measured(&self, part: Part, input: &str) -> Result<Measured, SolveError> {
+        let (parsed, parse) = measure(|| self.parse(input));
+        let parsed = parsed?;
+        let (answer, solve) = measure(|| match part {
+            Part::One => self.part_1(&parsed).map(|answer| answer.to_string()),
+            Part::Two => self.part_2(&parsed).map(|answer| answer.to_string()),
+        });
+        Ok(Measured {
+            answer: answer?,
+            parse,
+            solve,
+        })
+    }
+}
+
+/// A single half of a day's puzzle, as found in the `Registry`
+#[derive(Clone, Copy)]
+pub struct PartSolver<'a> {
+    pub day: u8,
+    pub part: Part,
+    puzzle: &'a dyn Puzzle,
+}
+
+impl PartSolver<'_> {
+    pub fn solve(&self, input: &str) -> Result<String, SolveError> {
+        self.puzzle.solve(self.part, input)
+    }
+
+    pub fn solve_measured(&self, input: &str) -> Result<Measured, SolveError> {
+        self.puzzle.solve_measured(self.part, input)
+    }
+}
+
+pub struct Registry {
+    puzzles: BTreeMap<u8, Box<dyn Puzzle>>,
+}
+
+impl Registry {
+    fn register(&mut self, puzzle: Box<dyn Puzzle>) {
+        self.puzzles.insert(puzzle.day(), puzzle);
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u8> + '_ {
+        self.puzzles.keys().copied()
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<PartSolver<'_>> {
+        self.puzzles.get(&day).map(|puzzle| PartSolver {
+            day,
+            part,
+            puzzle: puzzle.as_ref(),
+        })
+    }
+
+    /// Every (day, part) known, ordered by day then part
+    pub fn entries(&self) -> impl Iterator<Item = PartSolver<'_>> {
+        self.days()
+            .flat_map(move |day| Part::ALL.into_iter().filter_map(move |p| self.get(day, p)))
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Registry {
+            puzzles: BTreeMap::new(),
+        };
+        registry.register(Box::new(day_1::Day1));
+        registry.register(Box::new(day_2::Day2));
+        registry.register(Box::new(day_3::Day3));
+        registry.register(Box::new(day_4::Day4));
+        registry.register(Box::new(day_5::Day5));
+        registry.register(Box::new(day_6::Day6));
+        registry.register(Box::new(day_7::Day7));
+        registry.register(Box::new(day_8::Day8));
+        registry.register(Box::new(day_9::Day9));
+        registry.register(Box::new(day_10::Day10));
+        registry.register(Box::new(day_11::Day11));
+        registry.register(Box::new(day_12::Day12));
+        registry.register(Box::new(day_13::Day13));
+        registry.register(Box::new(day_14::Day14));
+        registry.register(Box::new(day_15::Day15));
+        registry.register(Box::new(day_16::Day16));
+        registry.register(Box::new(day_17::Day17));
+        registry.register(Box::new(day_18::Day18));
+        registry.register(Box::new(day_19::Day19));
+        registry.register(Box::new(day_20::Day20));
+        registry.register(Box::new(day_21::Day21));
+        registry.register(Box::new(day_22::Day22));
+        registry
+    }
+}
+
+/// Most inputs are one item per line, this keeps track of where parsing failed
+pub(crate) fn parse_lines<T, E: Display>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| ParseError::at_line(i + 1, e.to_string())))
+        .collect()
+}
+
+/// For parsers working on the whole input (nom): which line does `rest` start at.
+/// Line endings a separator left at the start of `rest` are skipped
+pub(crate) fn line_of(input: &str, rest: &str) -> usize {
+    let rest = rest.trim_start_matches(['\r', '\n']);
+    let consumed = input.len() - rest.len();
+    input[..consumed].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::Ledger;
+    use crate::solver::{
+        line_of, parse_lines, ParseError, Part, Puzzle, Registry, SolveError, Solver,
+    };
+    use crate::utils::io::{input_string, website_inputs};
+
+    #[test]
+    fn every_day_is_registered() {
+        let registry = Registry::default();
+        assert_eq!(
+            (1..=22).collect::<Vec<u8>>(),
+            registry.days().collect::<Vec<_>>()
+        );
+        assert_eq!(44, registry.entries().count());
+    }
+
+    #[test]
+    fn unknown_day() {
+        let registry = Registry::default();
+        assert!(registry.get(26, Part::One).is_none());
+    }
+
+    #[test]
+    fn solves_from_registry() {
+        let registry = Registry::default();
+        let input = input_string("day_9.txt").unwrap();
+        let solver = registry.get(9, Part::Two).unwrap();
+        assert_eq!(Ok("2653".to_string()), solver.solve(input.as_str()));
+    }
+
+    #[test]
+    fn matches_verified_answers() {
+        let registry = Registry::default();
+        let inputs = website_inputs();
+        let ledger = Ledger::for_inputs(&inputs).unwrap();
+        for (day, part, expected) in ledger.verified() {
+            let input = inputs.read(day).unwrap();
+            let solver = registry.get(day, part).unwrap();
+            assert_eq!(
+                Ok(expected.to_string()),
+                solver.solve(input.as_str()),
+                "day {day} part {part}"
+            );
+        }
+    }
+
+    struct Halfway;
+
+    impl Solver for Halfway {
+        const DAY: u8 = 26;
+        type Input = u32;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(&self, input: &str) -> Result<u32, ParseError> {
+            input.trim().parse().map_err(|_| ParseError::new("NaN"))
+        }
+
+        fn part_1(&self, input: &u32) -> Result<u32, SolveError> {
+            Ok(input * 2)
+        }
+
+        fn part_2(&self, _input: &u32) -> Result<u32, SolveError> {
+            Err(SolveError::Unsolved)
+        }
+    }
+
+    #[test]
+    fn unsolved_part() {
+        assert_eq!(Ok("42".to_string()), Halfway.solve(Part::One, "21"));
+        assert_eq!(Err(SolveError::Unsolved), Halfway.solve(Part::Two, "21"));
+    }
+
+    #[test]
+    fn reports_invalid_line() {
+        let registry = Registry::default();
+        let solver = registry.get(9, Part::One).unwrap();
+        let res = solver.solve("R 4\nU 4\nX 3\n");
+        assert!(matches!(
+            res,
+            Err(SolveError::Parse(ParseError { line: Some(3), .. }))
+        ));
+    }
+
+    #[test]
+    fn parses_line_by_line() {
+        let res = parse_lines("1\n2\nthree", str::parse::<u32>);
+        assert_eq!(Some(3), res.unwrap_err().line);
+        let res = parse_lines("1\n2\n3", str::parse::<u32>);
+        assert_eq!(Ok(vec![1, 2, 3]), res);
+    }
+
+    #[test]
+    fn finds_line_of_remaining_input() {
+        let input = "abc\ndef\nghi";
+        assert_eq!(1, line_of(input, input));
+        assert_eq!(2, line_of(input, &input[4..]));
+        assert_eq!(3, line_of(input, &input[9..]));
+        assert_eq!(2, line_of(input, &input[3..]));
+        let input = "abc\r\n\r\n\r\nghi";
+        assert_eq!(4, line_of(input, &input[3..]));
+    }
+}
@@ -1,12 +1,146 @@
 use crate::utils::geom::Direction::{Down, Left, Right, Up};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
-#[derive(Hash, Debug, PartialEq, Eq, Clone, Default)]
+/// `y` grows downwards, as rows of an input do
+#[derive(Hash, Debug, PartialEq, Eq, Clone, Copy, Default, PartialOrd, Ord)]
 pub(crate) struct Coord {
     pub(crate) x: i64,
     pub(crate) y: i64,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl Coord {
+    pub(crate) const fn new(x: i64, y: i64) -> Self {
+        Coord { x, y }
+    }
+
+    pub(crate) fn manhattan(&self, other: &Coord) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// As a king moves on a chessboard: diagonal steps count as one
+    pub(crate) fn chebyshev(&self, other: &Coord) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Steps between two hexagons, `x` and `y` being axial coordinates (see `HexDirection`)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn hex_distance(&self, other: &Coord) -> i64 {
+        let d = *self - *other;
+        (d.x.abs() + d.y.abs() + (d.x + d.y).abs()) / 2
+    }
+
+    /// Each component reduced to -1, 0 or 1
+    pub(crate) fn signum(&self) -> Coord {
+        Coord::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Coord) -> Self::Output {
+        Coord::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Coord) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Coord) -> Self::Output {
+        Coord::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Coord {
+    fn sub_assign(&mut self, rhs: Coord) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Coord {
+    type Output = Coord;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Coord::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Self::Output {
+        Coord::new(-self.x, -self.y)
+    }
+}
+
+/// A point in space, or a unit cube
+#[derive(Hash, Debug, PartialEq, Eq, Clone, Copy, Default, PartialOrd, Ord)]
+pub(crate) struct Coord3 {
+    pub(crate) x: i64,
+    pub(crate) y: i64,
+    pub(crate) z: i64,
+}
+
+impl Coord3 {
+    /// Unit steps along each axis, both ways
+    pub(crate) const DELTAS: [Coord3; 6] = [
+        Coord3::new(-1, 0, 0),
+        Coord3::new(1, 0, 0),
+        Coord3::new(0, -1, 0),
+        Coord3::new(0, 1, 0),
+        Coord3::new(0, 0, -1),
+        Coord3::new(0, 0, 1),
+    ];
+
+    pub(crate) const fn new(x: i64, y: i64, z: i64) -> Self {
+        Coord3 { x, y, z }
+    }
+
+    /// The 6 cubes sharing a face with this one
+    pub(crate) fn neighbours(&self) -> impl Iterator<Item = Coord3> + '_ {
+        Self::DELTAS.iter().map(move |delta| *self + *delta)
+    }
+}
+
+impl Add for Coord3 {
+    type Output = Coord3;
+
+    fn add(self, rhs: Coord3) -> Self::Output {
+        Coord3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Coord3 {
+    type Output = Coord3;
+
+    fn sub(self, rhs: Coord3) -> Self::Output {
+        Coord3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<i64> for Coord3 {
+    type Output = Coord3;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Coord3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Coord3 {
+    type Output = Coord3;
+
+    fn neg(self) -> Self::Output {
+        Coord3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub(crate) enum Direction {
     Up,
     Down,
@@ -15,3 +149,312 @@ pub(crate) enum Direction {
 }
 
 pub(crate) const DIRECTIONS: [Direction; 4] = [Left, Up, Right, Down];
+
+impl Direction {
+    pub(crate) fn delta(&self) -> Coord {
+        match self {
+            Up => Coord::new(0, -1),
+            Down => Coord::new(0, 1),
+            Left => Coord::new(-1, 0),
+            Right => Coord::new(1, 0),
+        }
+    }
+
+    /// Counter-clockwise quarter turn
+    pub(crate) fn turn_left(&self) -> Direction {
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    /// Clockwise quarter turn
+    pub(crate) fn turn_right(&self) -> Direction {
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub(crate) fn opposite(&self) -> Direction {
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+
+    /// Quarter turns clockwise from `Right`
+    pub(crate) fn facing(&self) -> usize {
+        match self {
+            Right => 0,
+            Down => 1,
+            Left => 2,
+            Up => 3,
+        }
+    }
+}
+
+/// The four directions plus the diagonals, clockwise from north (`Up`)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub(crate) enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    pub(crate) fn delta(&self) -> Coord {
+        match self {
+            Direction8::N => Coord::new(0, -1),
+            Direction8::NE => Coord::new(1, -1),
+            Direction8::E => Coord::new(1, 0),
+            Direction8::SE => Coord::new(1, 1),
+            Direction8::S => Coord::new(0, 1),
+            Direction8::SW => Coord::new(-1, 1),
+            Direction8::W => Coord::new(-1, 0),
+            Direction8::NW => Coord::new(-1, -1),
+        }
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl Direction8 {
+    pub(crate) const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|d| d == self).unwrap()
+    }
+
+    /// Eighth of a turn, counter-clockwise
+    pub(crate) fn turn_left(&self) -> Direction8 {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Eighth of a turn, clockwise
+    pub(crate) fn turn_right(&self) -> Direction8 {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    pub(crate) fn opposite(&self) -> Direction8 {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Up => Direction8::N,
+            Right => Direction8::E,
+            Down => Direction8::S,
+            Left => Direction8::W,
+        }
+    }
+}
+
+/// Neighbours of a pointy-topped hexagon, in axial coordinates: `x` along the rows, `y` along the NW-SE diagonal
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub(crate) enum HexDirection {
+    NE,
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl HexDirection {
+    pub(crate) const ALL: [HexDirection; 6] = [
+        HexDirection::NE,
+        HexDirection::E,
+        HexDirection::SE,
+        HexDirection::SW,
+        HexDirection::W,
+        HexDirection::NW,
+    ];
+
+    pub(crate) fn delta(&self) -> Coord {
+        match self {
+            HexDirection::NE => Coord::new(1, -1),
+            HexDirection::E => Coord::new(1, 0),
+            HexDirection::SE => Coord::new(0, 1),
+            HexDirection::SW => Coord::new(-1, 1),
+            HexDirection::W => Coord::new(-1, 0),
+            HexDirection::NW => Coord::new(0, -1),
+        }
+    }
+
+    pub(crate) fn opposite(&self) -> HexDirection {
+        let i = Self::ALL.iter().position(|d| d == self).unwrap();
+        Self::ALL[(i + 3) % 6]
+    }
+}
+
+/// Smallest rectangle holding a set of coordinates, both corners included
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub(crate) struct BoundingBox {
+    pub(crate) min: Coord,
+    pub(crate) max: Coord,
+}
+
+impl BoundingBox {
+    pub(crate) fn new(coord: Coord) -> Self {
+        BoundingBox {
+            min: coord,
+            max: coord,
+        }
+    }
+
+    pub(crate) fn extend(&mut self, coord: &Coord) {
+        self.min = Coord::new(self.min.x.min(coord.x), self.min.y.min(coord.y));
+        self.max = Coord::new(self.max.x.max(coord.x), self.max.y.max(coord.y));
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl BoundingBox {
+    /// `None` when there's nothing to hold
+    pub(crate) fn around<'a>(coords: impl IntoIterator<Item = &'a Coord>) -> Option<Self> {
+        let mut coords = coords.into_iter();
+        let mut bbox = BoundingBox::new(*coords.next()?);
+        for coord in coords {
+            bbox.extend(coord);
+        }
+        Some(bbox)
+    }
+
+    pub(crate) fn contains(&self, coord: &Coord) -> bool {
+        (self.min.x..=self.max.x).contains(&coord.x) && (self.min.y..=self.max.y).contains(&coord.y)
+    }
+
+    pub(crate) fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub(crate) fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    /// Row by row
+    pub(crate) fn coords(&self) -> impl Iterator<Item = Coord> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Coord::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::geom::{
+        BoundingBox, Coord, Coord3, Direction, Direction8, HexDirection, DIRECTIONS,
+    };
+
+    #[test]
+    fn arithmetic() {
+        let a = Coord::new(1, 2);
+        let b = Coord::new(-3, 5);
+        assert_eq!(Coord::new(-2, 7), a + b);
+        assert_eq!(Coord::new(4, -3), a - b);
+        assert_eq!(Coord::new(3, 6), a * 3);
+        assert_eq!(Coord::new(-1, -2), -a);
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+        assert_eq!(Coord::new(-1, 1), b.signum());
+    }
+
+    #[test]
+    fn distances() {
+        let a = Coord::new(1, 2);
+        let b = Coord::new(-3, 5);
+        assert_eq!(7, a.manhattan(&b));
+        assert_eq!(4, a.chebyshev(&b));
+        assert_eq!(0, a.manhattan(&a));
+        let origin = Coord::default();
+        for direction in HexDirection::ALL {
+            assert_eq!(1, origin.hex_distance(&direction.delta()));
+        }
+        assert_eq!(
+            3,
+            origin.hex_distance(&(HexDirection::E.delta() * 2 + HexDirection::SE.delta()))
+        );
+    }
+
+    #[test]
+    fn neighbours_in_space() {
+        let origin = Coord3::default();
+        let neighbours = origin.neighbours().collect::<Vec<_>>();
+        assert_eq!(6, neighbours.len());
+        for neighbour in &neighbours {
+            assert_eq!(1, neighbour.x.abs() + neighbour.y.abs() + neighbour.z.abs());
+            assert!(neighbours.contains(&(origin - *neighbour)));
+        }
+        assert_eq!(
+            Coord3::new(2, 1, 3),
+            Coord3::new(1, 1, 1) + Coord3::new(1, 0, 2)
+        );
+        assert_eq!(Coord3::new(-2, 0, 4), -Coord3::new(1, 0, -2) * 2);
+    }
+
+    #[test]
+    fn turns() {
+        for direction in DIRECTIONS {
+            assert_eq!(direction, direction.turn_left().turn_right());
+            assert_eq!(direction.opposite(), direction.turn_right().turn_right());
+            assert_eq!(-direction.delta(), direction.opposite().delta());
+            assert_eq!(direction.delta(), Direction8::from(direction).delta());
+        }
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(3, Direction::Up.facing());
+        for direction in DIRECTIONS {
+            assert_eq!(
+                (direction.facing() + 1) % 4,
+                direction.turn_right().facing()
+            );
+        }
+        assert_eq!(Coord::new(0, -1), Direction::Up.delta());
+        assert_eq!(Direction8::NE, Direction8::N.turn_right());
+        assert_eq!(Direction8::NW, Direction8::N.turn_left());
+        for direction in Direction8::ALL {
+            assert_eq!(-direction.delta(), direction.opposite().delta());
+        }
+        for direction in HexDirection::ALL {
+            assert_eq!(-direction.delta(), direction.opposite().delta());
+        }
+    }
+
+    #[test]
+    fn bounding_box() {
+        assert_eq!(None, BoundingBox::around(&[]));
+        let coords = [Coord::new(2, 3), Coord::new(-1, 5), Coord::new(0, 4)];
+        let bbox = BoundingBox::around(&coords).unwrap();
+        assert_eq!(Coord::new(-1, 3), bbox.min);
+        assert_eq!(Coord::new(2, 5), bbox.max);
+        assert_eq!((4, 3), (bbox.width(), bbox.height()));
+        assert!(bbox.contains(&Coord::new(1, 4)));
+        assert!(!bbox.contains(&Coord::new(3, 4)));
+        assert_eq!(12, bbox.coords().count());
+        assert_eq!(Some(Coord::new(-1, 3)), bbox.coords().next());
+    }
+}
@@ -0,0 +1,365 @@
+use crate::solver::ParseError;
+use crate::utils::geom::{Coord, Direction, Direction8, DIRECTIONS};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A rectangle of cells, stored row by row. `Coord::x` is the column, `Coord::y` the row (growing downwards)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Fails unless every row has the same length
+    pub(crate) fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let width = rows.first().map(Vec::len).unwrap_or(0);
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {y} has {} cells, expecting {width}",
+                rows[y].len()
+            ));
+        }
+        let height = rows.len();
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// One row per line, one cell per char
+    pub(crate) fn parse<E: Display>(
+        input: &str,
+        cell: impl Fn(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut rows: Vec<Vec<T>> = vec![];
+        for (i, line) in input.lines().enumerate() {
+            let row = line
+                .chars()
+                .map(&cell)
+                .collect::<Result<Vec<T>, E>>()
+                .map_err(|e| ParseError::at_line(i + 1, e.to_string()))?;
+            if let Some(first) = rows.first() {
+                if first.len() != row.len() {
+                    return Err(ParseError::at_line(
+                        i + 1,
+                        format!("{} cells, expecting {}", row.len(), first.len()),
+                    ));
+                }
+            }
+            rows.push(row);
+        }
+        if rows.first().is_none_or(Vec::is_empty) {
+            return Err(ParseError::new("The grid is empty"));
+        }
+        Grid::from_rows(rows).map_err(ParseError::new)
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    pub(crate) fn contains(&self, coord: &Coord) -> bool {
+        (0..self.width as i64).contains(&coord.x) && (0..self.height as i64).contains(&coord.y)
+    }
+
+    fn offset(&self, coord: &Coord) -> Option<usize> {
+        if self.contains(coord) {
+            Some(coord.y as usize * self.width + coord.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn coord_at(&self, offset: usize) -> Coord {
+        Coord {
+            x: (offset % self.width) as i64,
+            y: (offset / self.width) as i64,
+        }
+    }
+
+    pub(crate) fn get(&self, coord: &Coord) -> Option<&T> {
+        self.offset(coord).map(|i| &self.cells[i])
+    }
+
+    pub(crate) fn get_mut(&mut self, coord: &Coord) -> Option<&mut T> {
+        self.offset(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Every coordinate, row by row
+    pub(crate) fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.cells.len()).map(|i| self.coord_at(i))
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.coord_at(i), cell))
+    }
+
+    pub(crate) fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.coord_at(i))
+    }
+
+    pub(crate) fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// Left, up, right then down of `coord`, the ones inside the grid only
+    pub(crate) fn neighbours<'a>(&'a self, coord: &'a Coord) -> impl Iterator<Item = Coord> + 'a {
+        DIRECTIONS
+            .iter()
+            .map(move |direction| *coord + direction.delta())
+            .filter(|next| self.contains(next))
+    }
+
+    /// Cells from `from` (excluded) moving by `step`, until the edge of the grid
+    pub(crate) fn ray(&self, from: &Coord, step: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        let mut current = *from;
+        std::iter::from_fn(move || {
+            if step == Coord::default() {
+                return None;
+            }
+            current += step;
+            self.get(&current).map(|cell| (current, cell))
+        })
+    }
+
+    /// Cells seen from `from` (excluded) looking towards `direction`, closest first
+    pub(crate) fn towards(
+        &self,
+        from: &Coord,
+        direction: &Direction,
+    ) -> impl Iterator<Item = (Coord, &T)> {
+        self.ray(from, direction.delta())
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl<T> Grid<T> {
+    pub(crate) fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Neighbours including diagonals, clockwise from the upper one, the ones inside the grid only
+    pub(crate) fn neighbours_8<'a>(&'a self, coord: &'a Coord) -> impl Iterator<Item = Coord> + 'a {
+        Direction8::ALL
+            .iter()
+            .map(move |direction| *coord + direction.delta())
+            .filter(|next| self.contains(next))
+    }
+
+    pub(crate) fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub(crate) fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl<T: Clone> Grid<T> {
+    /// Rows become columns
+    pub(crate) fn transpose(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// The first column becomes the first row, read backwards
+    pub(crate) fn rotate_clockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    pub(crate) fn rotate_counter_clockwise(&self) -> Self {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<&Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: &Coord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is out of the grid"))
+    }
+}
+
+impl<T> IndexMut<&Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: &Coord) -> &mut Self::Output {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is out of the grid"))
+    }
+}
+
+/// Row by row, cells side by side
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            if y + 1 < self.height {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::ParseError;
+    use crate::utils::geom::{Coord, Direction};
+    use crate::utils::grid::Grid;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abc\ndef", Ok::<char, String>).unwrap()
+    }
+
+    fn c(x: i64, y: i64) -> Coord {
+        Coord { x, y }
+    }
+
+    #[test]
+    fn parses_rectangles_only() {
+        let grid = sample();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&'f'), grid.get(&c(2, 1)));
+        let res = Grid::parse("abc\nde\n", Ok::<char, String>);
+        assert_eq!(Some(2), res.unwrap_err().line);
+        let res = Grid::parse("12\n3x", |ch| ch.to_digit(10).ok_or("NaN"));
+        assert_eq!(
+            Err(ParseError::at_line(2, "NaN")),
+            res.map(|grid| grid.width())
+        );
+        assert!(Grid::parse("", Ok::<char, String>).is_err());
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn bounds_checked() {
+        let mut grid = sample();
+        assert_eq!(None, grid.get(&c(3, 0)));
+        assert_eq!(None, grid.get(&c(0, -1)));
+        assert_eq!('a', grid[&c(0, 0)]);
+        grid[&c(1, 1)] = 'x';
+        assert_eq!("abc\ndxf", grid.to_string());
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = sample();
+        assert_eq!("def", grid.row(1).collect::<String>());
+        assert_eq!("cf", grid.column(2).collect::<String>());
+        assert_eq!(Some(c(1, 1)), grid.position(|ch| *ch == 'e'));
+        assert_eq!(
+            vec![c(0, 0), c(1, 0), c(2, 0), c(0, 1), c(1, 1), c(2, 1)],
+            grid.coords().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn neighbours_inside_the_grid() {
+        let grid = sample();
+        assert_eq!(
+            vec![c(1, 0), c(0, 1)],
+            grid.neighbours(&c(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![c(0, 1), c(1, 0), c(2, 1)],
+            grid.neighbours(&c(1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![c(1, 0), c(2, 0), c(2, 1), c(0, 1), c(0, 0)],
+            grid.neighbours_8(&c(1, 1)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn casts_rays() {
+        let grid = Grid::parse("abcde\nfghij\nklmno", Ok::<char, String>).unwrap();
+        let seen = |from: Coord, direction: Direction| {
+            grid.towards(&from, &direction)
+                .map(|(_, ch)| *ch)
+                .collect::<String>()
+        };
+        assert_eq!("ba", seen(c(2, 0), Direction::Left));
+        assert_eq!("de", seen(c(2, 0), Direction::Right));
+        assert_eq!("", seen(c(2, 0), Direction::Up));
+        assert_eq!("hc", seen(c(2, 2), Direction::Up));
+        assert_eq!(
+            vec![(c(1, 1), &'g'), (c(2, 2), &'m')],
+            grid.ray(&c(0, 0), c(1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.ray(&c(0, 0), c(0, 0)).count());
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = sample();
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_clockwise().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_counter_clockwise().to_string());
+        assert_eq!(grid, grid.transpose().transpose());
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_clockwise();
+        }
+        assert_eq!(grid, rotated);
+        assert_eq!(
+            grid.rotate_counter_clockwise(),
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+        );
+    }
+
+    #[test]
+    fn maps_cells() {
+        let grid = Grid::filled(2, 2, 1).map(|n| n * 7);
+        assert_eq!("77\n77", grid.to_string());
+    }
+}
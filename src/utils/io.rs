@@ -1,32 +1,202 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::path::Path;
-use std::{fs, io};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{env, fmt, fs, io};
 
+#[cfg(test)]
+use std::{fs::File, io::BufRead};
+
+#[cfg(test)]
 pub(crate) type FileLines = io::Lines<io::BufReader<File>>;
 
+/// Directory containing the inputs, overriding the config file
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+/// Sub-directory of the inputs directory to read from, when inputs of several accounts are side by side
+pub const USER_ENV: &str = "AOC_USER";
+/// Path to the config file, defaults to `$XDG_CONFIG_HOME/aoc/config.json` or `~/.config/aoc/config.json`
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+const DEFAULT_INPUTS_DIR: &str = "website_inputs";
+
+#[derive(Debug)]
+pub enum InputError {
+    /// No input has been stored for this day
+    NotFound {
+        day: u8,
+        location: String,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    InvalidConfig {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, location } => {
+                write!(f, "no input for day {day} in {location}")
+            }
+            InputError::Io { path, source } => {
+                write!(f, "could not read {}: {source}", path.display())
+            }
+            InputError::InvalidConfig { path, reason } => {
+                write!(f, "invalid config file {}: {reason}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Where to find the puzzle inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `<dir>/day_<N>.txt`
+    Directory(PathBuf),
+    /// `<root>/<user>/day_<N>.txt`, so that inputs from several accounts can live side by side
+    UserDirectory { root: PathBuf, user: String },
+    /// The very same file, whatever the day
+    File(PathBuf),
+    /// Inputs by day, already loaded
+    Memory(BTreeMap<u8, String>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct Config {
+    pub(crate) inputs: Option<PathBuf>,
+    pub(crate) user: Option<String>,
+    pub(crate) session: Option<String>,
+}
+
+/// The config file is optional, an absent file is an empty config
+pub(crate) fn load_config(var: &impl Fn(&str) -> Option<String>) -> Result<Config, InputError> {
+    match config_path(var) {
+        Some(path) if path.exists() => read_config(&path),
+        _ => Ok(Config::default()),
+    }
+}
+
+impl InputSource {
+    pub fn in_memory(day: u8, input: impl Into<String>) -> Self {
+        InputSource::Memory(BTreeMap::from([(day, input.into())]))
+    }
+
+    /// Resolves from the environment variables, then the config file, then `./website_inputs`
+    pub fn from_env() -> Result<Self, InputError> {
+        Self::resolve(|key| env::var(key).ok())
+    }
+
+    fn resolve(var: impl Fn(&str) -> Option<String>) -> Result<Self, InputError> {
+        let config = load_config(&var)?;
+        let dir = var(INPUTS_ENV)
+            .map(PathBuf::from)
+            .or(config.inputs)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUTS_DIR));
+        Ok(match var(USER_ENV).or(config.user) {
+            Some(user) => InputSource::UserDirectory { root: dir, user },
+            None => InputSource::Directory(dir),
+        })
+    }
+
+    /// Where the input of `day` is (or would be) stored, `None` for in-memory inputs
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        let file_name = format!("day_{day}.txt");
+        match self {
+            InputSource::Directory(dir) => Some(dir.join(file_name)),
+            InputSource::UserDirectory { root, user } => Some(root.join(user).join(file_name)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Memory(_) => None,
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        match self {
+            InputSource::Memory(inputs) => inputs.get(&day).cloned().ok_or(InputError::NotFound {
+                day,
+                location: "memory".to_string(),
+            }),
+            _ => {
+                let path = self.path(day).unwrap();
+                fs::read_to_string(&path).map_err(|source| match source.kind() {
+                    io::ErrorKind::NotFound => InputError::NotFound {
+                        day,
+                        location: path.display().to_string(),
+                    },
+                    _ => InputError::Io { path, source },
+                })
+            }
+        }
+    }
+}
+
+fn config_path(var: &impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    if let Some(path) = var(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join("aoc").join("config.json"))
+}
+
+/// A relative `inputs` directory is relative to the config file itself
+fn read_config(path: &Path) -> Result<Config, InputError> {
+    let content = fs::read_to_string(path).map_err(|source| InputError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut config: Config =
+        serde_json::from_str(&content).map_err(|e| InputError::InvalidConfig {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+    if let (Some(inputs), Some(parent)) = (&config.inputs, path.parent()) {
+        config.inputs = Some(parent.join(inputs));
+    }
+    Ok(config)
+}
+
+/// The inputs checked in alongside the sources, used by tests
+#[cfg(test)]
+fn website_inputs_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_INPUTS_DIR)
+}
+
+#[cfg(test)]
+pub(crate) fn website_inputs() -> InputSource {
+    InputSource::Directory(website_inputs_dir())
+}
+
+#[cfg(test)]
 pub(crate) fn input_file_lines<P>(filename: P) -> io::Result<FileLines>
 where
     P: AsRef<Path>,
 {
-    let project_root = env!("CARGO_MANIFEST_DIR");
-    let project_root = Path::new(project_root);
-    let samples_dir = project_root.join("website_inputs");
-    let path = samples_dir.join(filename);
-    read_lines(path)
+    read_lines(website_inputs_dir().join(filename))
 }
 
-pub(crate) fn input_string<P>(filename: P) -> String
+#[cfg(test)]
+pub(crate) fn input_string<P>(filename: P) -> io::Result<String>
 where
     P: AsRef<Path>,
 {
-    let project_root = env!("CARGO_MANIFEST_DIR");
-    let project_root = Path::new(project_root);
-    let samples_dir = project_root.join("website_inputs");
-    let path = samples_dir.join(filename);
-    fs::read_to_string(path).unwrap()
+    fs::read_to_string(website_inputs_dir().join(filename))
 }
 
+#[cfg(test)]
 fn read_lines<P>(path: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
@@ -37,7 +207,26 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::io::input_file_lines;
+    use crate::utils::io::{
+        input_file_lines, website_inputs, InputError, InputSource, CONFIG_ENV, INPUTS_ENV, USER_ENV,
+    };
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_io_{name}_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
 
     #[test]
     fn can_read_sample() {
@@ -48,4 +237,119 @@ mod tests {
             assert!(line.is_ok());
         }
     }
+
+    #[test]
+    fn reads_from_directory() {
+        let input = website_inputs().read(6);
+        assert!(input.unwrap().starts_with("stftmtvv"));
+    }
+
+    #[test]
+    fn missing_day_is_not_found() {
+        let res = website_inputs().read(25);
+        assert!(matches!(res, Err(InputError::NotFound { day: 25, .. })));
+        let res = InputSource::in_memory(1, "1000").read(2);
+        assert!(matches!(res, Err(InputError::NotFound { day: 2, .. })));
+    }
+
+    #[test]
+    fn reads_from_memory() {
+        let source = InputSource::in_memory(1, "1000\n2000");
+        assert_eq!("1000\n2000", source.read(1).unwrap());
+        assert_eq!(None, source.path(1));
+    }
+
+    #[test]
+    fn explicit_file_whatever_the_day() {
+        let source = InputSource::File(PathBuf::from("/tmp/mine.txt"));
+        assert_eq!(Some(PathBuf::from("/tmp/mine.txt")), source.path(3));
+        assert_eq!(Some(PathBuf::from("/tmp/mine.txt")), source.path(12));
+    }
+
+    #[test]
+    fn defaults_to_local_website_inputs() {
+        let source = InputSource::resolve(vars(&[])).unwrap();
+        assert_eq!(
+            InputSource::Directory(PathBuf::from("website_inputs")),
+            source
+        );
+    }
+
+    #[test]
+    fn resolves_from_env() {
+        let source = InputSource::resolve(vars(&[(INPUTS_ENV, "/srv/aoc")])).unwrap();
+        assert_eq!(Some(PathBuf::from("/srv/aoc/day_4.txt")), source.path(4));
+        let source =
+            InputSource::resolve(vars(&[(INPUTS_ENV, "/srv/aoc"), (USER_ENV, "alice")])).unwrap();
+        assert_eq!(
+            Some(PathBuf::from("/srv/aoc/alice/day_4.txt")),
+            source.path(4)
+        );
+    }
+
+    #[test]
+    fn resolves_from_config_file() {
+        let dir = scratch_dir("config");
+        let config = dir.join("config.json");
+        fs::write(&config, r#"{"inputs": "inputs", "user": "bob"}"#).unwrap();
+        let config_path = config.to_str().unwrap();
+
+        let source = InputSource::resolve(vars(&[(CONFIG_ENV, config_path)])).unwrap();
+        assert_eq!(
+            InputSource::UserDirectory {
+                root: dir.join("inputs"),
+                user: "bob".to_string()
+            },
+            source
+        );
+        // env takes precedence over the config file
+        let source = InputSource::resolve(vars(&[
+            (CONFIG_ENV, config_path),
+            (INPUTS_ENV, "/srv/aoc"),
+            (USER_ENV, "alice"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            Some(PathBuf::from("/srv/aoc/alice/day_1.txt")),
+            source.path(1)
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn looks_for_config_in_home() {
+        let home = scratch_dir("home");
+        let config_dir = home.join(".config").join("aoc");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("config.json"), r#"{"inputs": "/srv/aoc"}"#).unwrap();
+        let source = InputSource::resolve(vars(&[("HOME", home.to_str().unwrap())])).unwrap();
+        assert_eq!(InputSource::Directory(PathBuf::from("/srv/aoc")), source);
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn reports_invalid_config() {
+        let dir = scratch_dir("invalid_config");
+        let config = dir.join("config.json");
+        fs::write(&config, "inputs = /srv/aoc").unwrap();
+        let res = InputSource::resolve(vars(&[(CONFIG_ENV, config.to_str().unwrap())]));
+        assert!(matches!(res, Err(InputError::InvalidConfig { .. })));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn user_directories_side_by_side() {
+        let root = scratch_dir("users");
+        for user in ["alice", "bob"] {
+            fs::create_dir_all(root.join(user)).unwrap();
+            fs::write(root.join(user).join("day_1.txt"), user).unwrap();
+        }
+        let source = |user: &str| InputSource::UserDirectory {
+            root: root.clone(),
+            user: user.to_string(),
+        };
+        assert_eq!("alice", source("alice").read(1).unwrap());
+        assert_eq!("bob", source("bob").read(1).unwrap());
+        fs::remove_dir_all(root).unwrap();
+    }
 }
@@ -1,2 +1,4 @@
 pub(crate) mod geom;
-pub(crate) mod io;
+pub(crate) mod grid;
+pub mod io;
+pub(crate) mod ranges;
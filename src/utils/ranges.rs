@@ -0,0 +1,124 @@
+use std::ops::RangeInclusive;
+
+/// `outer` holds every value of `inner`
+pub(crate) fn contains<T: PartialOrd>(
+    outer: &RangeInclusive<T>,
+    inner: &RangeInclusive<T>,
+) -> bool {
+    outer.contains(inner.start()) && outer.contains(inner.end())
+}
+
+/// At least one value is in both ranges
+pub(crate) fn overlap<T: PartialOrd>(lhs: &RangeInclusive<T>, rhs: &RangeInclusive<T>) -> bool {
+    lhs.start() <= rhs.end() && rhs.start() <= lhs.end()
+}
+
+/// Disjoint ranges, sorted, merged as soon as they overlap or touch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RangeSet {
+    ranges: Vec<RangeInclusive<i64>>,
+}
+
+impl RangeSet {
+    /// How many values the ranges hold
+    pub(crate) fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| (range.end() - range.start() + 1) as u64)
+            .sum()
+    }
+
+    pub(crate) fn contains(&self, value: i64) -> bool {
+        self.ranges.iter().any(|range| range.contains(&value))
+    }
+
+    /// Parts of `bounds` none of the ranges hold
+    pub(crate) fn gaps(&self, bounds: RangeInclusive<i64>) -> Vec<RangeInclusive<i64>> {
+        let (mut next, end) = bounds.into_inner();
+        let mut gaps = vec![];
+        for range in &self.ranges {
+            if next > end {
+                break;
+            }
+            if *range.start() > next {
+                gaps.push(next..=(*range.start() - 1).min(end));
+            }
+            next = next.max(*range.end() + 1);
+        }
+        if next <= end {
+            gaps.push(next..=end);
+        }
+        gaps
+    }
+}
+
+/// Empty ranges are ignored
+impl FromIterator<RangeInclusive<i64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(iter: I) -> Self {
+        let mut sorted = iter
+            .into_iter()
+            .filter(|range| range.start() <= range.end())
+            .collect::<Vec<_>>();
+        sorted.sort_unstable_by_key(|range| *range.start());
+        let mut ranges: Vec<RangeInclusive<i64>> = Vec::with_capacity(sorted.len());
+        for range in sorted {
+            match ranges.last_mut() {
+                Some(last) if *range.start() <= last.end() + 1 => {
+                    if range.end() > last.end() {
+                        *last = *last.start()..=*range.end();
+                    }
+                }
+                _ => ranges.push(range),
+            }
+        }
+        RangeSet { ranges }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::ranges::{contains, overlap, RangeSet};
+    use std::ops::RangeInclusive;
+
+    #[test]
+    fn compares_ranges() {
+        assert!(contains(&(2..=8), &(4..=6)));
+        assert!(contains(&(2..=8), &(2..=8)));
+        assert!(!contains(&(4..=6), &(2..=8)));
+        assert!(overlap(&(1..=2), &(2..=4)));
+        assert!(overlap(&(6..=8), &(2..=7)));
+        assert!(!overlap(&(1..=3), &(4..=4)));
+    }
+
+    #[test]
+    fn merges_ranges() {
+        let set = [10..=12, 0..=2, 5..=6].into_iter().collect::<RangeSet>();
+        assert_eq!(vec![0..=2, 5..=6, 10..=12], set.ranges);
+        let set = [10..=12, 0..=2, 5..=6, 3..=4]
+            .into_iter()
+            .collect::<RangeSet>();
+        assert_eq!(vec![0..=6, 10..=12], set.ranges);
+        let set = [
+            8..=20,
+            3..=4,
+            0..=2,
+            5..=6,
+            10..=12,
+            RangeInclusive::new(30, 25),
+        ]
+        .into_iter()
+        .collect::<RangeSet>();
+        assert_eq!(vec![0..=6, 8..=20], set.ranges);
+        assert_eq!(20, set.len());
+        assert!(set.contains(8) && !set.contains(7));
+    }
+
+    #[test]
+    fn finds_gaps() {
+        let set = [0..=6, 8..=20].into_iter().collect::<RangeSet>();
+        assert_eq!(vec![7..=7], set.gaps(0..=20));
+        assert_eq!(vec![-2..=-1, 7..=7, 21..=22], set.gaps(-2..=22));
+        assert_eq!(Vec::<RangeInclusive<i64>>::new(), set.gaps(10..=12));
+        assert_eq!(vec![0..=5], RangeSet::default().gaps(0..=5));
+    }
+}